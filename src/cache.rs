@@ -105,7 +105,8 @@ impl Cache {
             let entry = entry?;
             let path = entry.path();
             let ext = path.extension().and_then(|e| e.to_str());
-            if ext == Some("json") || ext == Some("parquet") {
+            // `.part` and `.etag` files are interrupted downloads
+            if matches!(ext, Some("json" | "parquet" | "part" | "etag")) {
                 std::fs::remove_file(path)?;
             }
         }
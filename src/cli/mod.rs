@@ -1,6 +1,10 @@
 //! CLI argument definitions.
 
+use crate::schema::TREND_FIELDS;
+use chrono::NaiveDate;
+use clap::builder::{FalseyValueParser, PossibleValuesParser};
 use clap::{Parser, Subcommand};
+use std::path::PathBuf;
 
 /// which-llm - Query AI model benchmarks from the terminal.
 #[derive(Parser, Debug)]
@@ -17,6 +21,20 @@ pub struct Cli {
     #[arg(long, global = true)]
     pub use_api: bool,
 
+    /// Fetch hosted data from this GitHub release tag (e.g. data/2026-10-01)
+    /// instead of data/latest
+    #[arg(long, global = true, value_name = "TAG")]
+    pub data_tag: Option<String>,
+
+    /// Never use the network; use cached data even when stale
+    #[arg(
+        long,
+        global = true,
+        env = crate::offline::OFFLINE_ENV,
+        value_parser = FalseyValueParser::new()
+    )]
+    pub offline: bool,
+
     /// Suppress output messages (for scripting)
     #[arg(long, short = 'q', global = true)]
     pub quiet: bool,
@@ -26,24 +44,78 @@ pub struct Cli {
 pub enum Commands {
     /// Execute SQL queries against cached data
     Query {
-        /// SQL query to execute (e.g., "SELECT * FROM benchmarks WHERE intelligence > 40")
+        #[command(subcommand)]
+        command: Option<QueryCommands>,
+
+        /// SQL query to execute (e.g., "SELECT * FROM benchmarks WHERE intelligence > 40").
+        /// Use "-" to read a script from stdin. Omit to start an interactive SQL shell.
         sql: Option<String>,
 
+        /// Run a SQL script of `;`-separated statements from a file
+        #[arg(long, short = 'f', value_name = "FILE", conflicts_with = "sql")]
+        file: Option<PathBuf>,
+
         /// Output as JSON
-        #[arg(long, conflicts_with_all = ["csv", "table", "plain"])]
+        #[arg(long, global = true, conflicts_with_all = ["csv", "table", "plain"])]
         json: bool,
 
         /// Output as CSV
-        #[arg(long, conflicts_with_all = ["json", "table", "plain"])]
+        #[arg(long, global = true, conflicts_with_all = ["json", "table", "plain"])]
         csv: bool,
 
         /// Output as ASCII table
-        #[arg(long, conflicts_with_all = ["json", "csv", "plain"])]
+        #[arg(long, global = true, conflicts_with_all = ["json", "csv", "plain"])]
         table: bool,
 
         /// Output as plain text (tab-separated)
-        #[arg(long, conflicts_with_all = ["json", "csv", "table"])]
+        #[arg(long, global = true, conflicts_with_all = ["json", "csv", "table"])]
         plain: bool,
+
+        /// Decimal places for floats in markdown, table and plain output
+        /// (JSON and CSV always keep full precision)
+        #[arg(long, global = true, default_value_t = crate::query::DEFAULT_PRECISION)]
+        precision: usize,
+
+        /// Cancel the query after this many seconds (rows fetched so far are
+        /// shown, marked as truncated)
+        #[arg(long, global = true, value_name = "SECS")]
+        timeout: Option<u64>,
+
+        /// Return at most this many rows (output is marked as truncated)
+        #[arg(long, global = true, value_name = "N")]
+        max_rows: Option<usize>,
+
+        /// DuckDB memory limit for the query (e.g., 512MB, 2GB)
+        #[arg(long, global = true, value_name = "SIZE")]
+        memory_limit: Option<String>,
+
+        /// Write results to a file instead of stdout; the format is inferred
+        /// from the extension (.parquet, .arrow, .ndjson, .csv, .json)
+        #[arg(long, global = true, short = 'o', value_name = "FILE")]
+        out: Option<PathBuf>,
+
+        /// Show the SQL sent to DuckDB, the views it reads and the query plan
+        /// without executing it
+        #[arg(long, global = true, alias = "show-sql", conflicts_with = "out")]
+        explain: bool,
+
+        /// Query the data snapshot in effect on this date (YYYY-MM-DD)
+        #[arg(
+            long,
+            global = true,
+            value_name = "DATE",
+            value_parser = crate::history::parse_date
+        )]
+        as_of: Option<NaiveDate>,
+
+        /// Bind a `:name` placeholder in the query (repeatable)
+        #[arg(
+            long = "param",
+            global = true,
+            value_name = "NAME=VALUE",
+            value_parser = crate::query::params::parse_param
+        )]
+        params: Vec<(String, String)>,
     },
 
     /// Refresh all cached data from sources
@@ -65,6 +137,10 @@ pub enum Commands {
         #[arg(long, short = 'v')]
         verbose: bool,
 
+        /// Compare the data snapshot in effect on this date (YYYY-MM-DD)
+        #[arg(long, value_name = "DATE", value_parser = crate::history::parse_date)]
+        as_of: Option<NaiveDate>,
+
         /// Output as JSON
         #[arg(long, conflicts_with_all = ["csv", "table", "plain"])]
         json: bool,
@@ -104,6 +180,37 @@ pub enum Commands {
         #[arg(long, default_value = "once")]
         period: String,
 
+        /// Use the prices from the data snapshot in effect on this date (YYYY-MM-DD)
+        #[arg(long, value_name = "DATE", value_parser = crate::history::parse_date)]
+        as_of: Option<NaiveDate>,
+
+        /// Output as JSON
+        #[arg(long, conflicts_with_all = ["csv", "table", "plain"])]
+        json: bool,
+
+        /// Output as CSV
+        #[arg(long, conflicts_with_all = ["json", "table", "plain"])]
+        csv: bool,
+
+        /// Output as ASCII table
+        #[arg(long, conflicts_with_all = ["json", "csv", "plain"])]
+        table: bool,
+
+        /// Output as plain text (tab-separated)
+        #[arg(long, conflicts_with_all = ["json", "csv", "table"])]
+        plain: bool,
+    },
+
+    /// Compare two data snapshots: new and removed models, price, score
+    /// and capability changes
+    Diff {
+        /// Older snapshot: a date (YYYY-MM-DD) or a directory of Parquet files
+        /// [default: the snapshot before the latest]
+        from: Option<String>,
+
+        /// Newer snapshot: a date or a directory [default: the current cache]
+        to: Option<String>,
+
         /// Output as JSON
         #[arg(long, conflicts_with_all = ["csv", "table", "plain"])]
         json: bool,
@@ -121,6 +228,45 @@ pub enum Commands {
         plain: bool,
     },
 
+    /// Show how models changed across the stored data snapshots
+    Trend {
+        /// Model names to chart (fuzzy matched)
+        #[arg(required = true)]
+        models: Vec<String>,
+
+        /// Fields to chart, comma-separated
+        #[arg(
+            long,
+            short = 'f',
+            value_delimiter = ',',
+            default_values = ["price", "intelligence", "tps"],
+            value_parser = PossibleValuesParser::new(TREND_FIELDS.iter().copied())
+        )]
+        field: Vec<String>,
+
+        /// Output as JSON
+        #[arg(long, conflicts_with_all = ["csv", "table", "plain"])]
+        json: bool,
+
+        /// Output as CSV
+        #[arg(long, conflicts_with_all = ["json", "table", "plain"])]
+        csv: bool,
+
+        /// Output as ASCII table
+        #[arg(long, conflicts_with_all = ["json", "csv", "plain"])]
+        table: bool,
+
+        /// Output as plain text (tab-separated)
+        #[arg(long, conflicts_with_all = ["json", "csv", "table"])]
+        plain: bool,
+    },
+
+    /// Watch models and alert when they cross price or score thresholds
+    Watch {
+        #[command(subcommand)]
+        command: WatchCommands,
+    },
+
     /// Show data source information and attribution
     Info,
 
@@ -163,6 +309,78 @@ pub fn get_output_format(
     }
 }
 
+#[derive(Subcommand, Debug)]
+pub enum QueryCommands {
+    /// Save a named query (SQL may contain `:name` placeholders)
+    Save {
+        /// Query name
+        name: String,
+        /// SQL to save
+        sql: String,
+        /// Short description shown by `query list`
+        #[arg(long, short = 'd')]
+        description: Option<String>,
+    },
+    /// Run a saved query
+    Run {
+        /// Query name
+        name: String,
+    },
+    /// List saved queries
+    List,
+    /// Delete a saved query
+    Delete {
+        /// Query name
+        name: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum WatchCommands {
+    /// Watch a model (replaces its earlier thresholds)
+    Add {
+        /// Model name or slug (must match a single model)
+        model: String,
+        /// Maximum blended price (USD per 1M tokens)
+        #[arg(long)]
+        max_price: Option<f64>,
+        /// Maximum input price (USD per 1M tokens)
+        #[arg(long)]
+        max_input_price: Option<f64>,
+        /// Maximum output price (USD per 1M tokens)
+        #[arg(long)]
+        max_output_price: Option<f64>,
+        /// Minimum intelligence index
+        #[arg(long)]
+        min_intelligence: Option<f64>,
+        /// Minimum coding index
+        #[arg(long)]
+        min_coding: Option<f64>,
+        /// Minimum output speed (tokens/s)
+        #[arg(long)]
+        min_tps: Option<f64>,
+        /// Maximum time to first token (seconds)
+        #[arg(long)]
+        max_latency: Option<f64>,
+    },
+    /// List watched models
+    List,
+    /// Stop watching a model
+    Remove {
+        /// Model slug
+        model: String,
+    },
+    /// Check watched models against the cached data; fails on any violation
+    Check {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+        /// POST violations to this URL (overrides `webhook` under [watch])
+        #[arg(long, value_name = "URL")]
+        webhook: Option<String>,
+    },
+}
+
 #[derive(Subcommand, Debug)]
 pub enum CacheCommands {
     /// Clear all cached data
@@ -180,6 +398,9 @@ pub enum ProfileCommands {
         /// API key (will prompt if not provided)
         #[arg(long)]
         api_key: Option<String>,
+        /// Fetch hosted data from this base URL (http(s):// mirror or file:// directory)
+        #[arg(long, value_name = "URL")]
+        data_url: Option<String>,
     },
     /// List all profiles
     List,
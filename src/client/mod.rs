@@ -9,14 +9,17 @@
 //! - `models`: Pure models.dev data (capabilities, limits, provider info)
 
 use crate::cache::Cache;
+use crate::config::Config;
 use crate::error::{AppError, Result};
+use crate::history;
 use crate::models::{LlmModel, MediaModel};
 use crate::parquet;
-use crate::remote::RemoteDataClient;
+use crate::remote::{DataSource, RemoteDataClient};
 use crate::sources::artificial_analysis::models::{AaLlmModel, AaLlmRow};
 use crate::sources::artificial_analysis::AaClient;
 use crate::sources::models_dev::models::{flatten_response, ModelsDevRow};
 use crate::sources::models_dev::ModelsDevClient;
+use chrono::NaiveDate;
 use duckdb::Connection;
 use std::path::Path;
 
@@ -28,10 +31,16 @@ pub struct HostedDataClient {
 }
 
 impl HostedDataClient {
-    /// Create a new hosted data client.
+    /// Create a new hosted data client for the configured data source.
     pub fn new() -> Result<Self> {
+        let config = Config::load()?;
+        Self::with_source(DataSource::resolve(&config, None, None)?)
+    }
+
+    /// Create a hosted data client fetching from `source`.
+    pub fn with_source(source: DataSource) -> Result<Self> {
         let cache = Cache::new()?;
-        let remote = RemoteDataClient::new(cache.base_dir().to_path_buf())?;
+        let remote = RemoteDataClient::with_source(cache.base_dir().to_path_buf(), source)?;
         Ok(Self { remote, cache })
     }
 
@@ -54,6 +63,33 @@ impl HostedDataClient {
         self.load_llms_from_parquet(&parquet_path)
     }
 
+    /// Load LLM models from the snapshot in effect on `as_of`.
+    pub fn get_llm_models_as_of(&self, as_of: NaiveDate) -> Result<Vec<LlmModel>> {
+        let snapshot = history::snapshot_as_of(self.cache.base_dir(), as_of)?;
+        let parquet_path = snapshot.dir.join("benchmarks.parquet");
+        if !parquet_path.exists() {
+            return Err(AppError::NotFound(format!(
+                "benchmarks in the {} snapshot",
+                snapshot.date
+            )));
+        }
+        self.load_llms_from_parquet(&parquet_path)
+    }
+
+    /// Load LLM models from every snapshot, oldest first.
+    ///
+    /// Snapshots without benchmarks are skipped.
+    pub fn get_llm_models_history(&self) -> Result<Vec<(NaiveDate, Vec<LlmModel>)>> {
+        let mut history = Vec::new();
+        for snapshot in history::list_snapshots(self.cache.base_dir())? {
+            let parquet_path = snapshot.dir.join("benchmarks.parquet");
+            if parquet_path.exists() {
+                history.push((snapshot.date, self.load_llms_from_parquet(&parquet_path)?));
+            }
+        }
+        Ok(history)
+    }
+
     /// Load LLM models from a parquet file.
     fn load_llms_from_parquet(&self, path: &Path) -> Result<Vec<LlmModel>> {
         let conn = Connection::open_in_memory()
@@ -3,7 +3,7 @@
 //! Calculate token costs for one or more LLM models.
 
 use crate::error::{AppError, Result};
-use crate::models::LlmModel;
+use crate::models::{LlmModel, DAYS_PER_MONTH, TOKENS_PER_PRICE_UNIT};
 use crate::output::{format_output, Formattable, OutputFormat};
 use crate::utils::{find_models_by_names, parse_tokens};
 use serde::Serialize;
@@ -120,10 +120,10 @@ fn calculate_cost(
     // Prices are per million tokens
     let input_cost = model
         .input_price
-        .map(|p| (input_tokens as f64 / 1_000_000.0) * p);
+        .map(|p| (input_tokens as f64 / TOKENS_PER_PRICE_UNIT) * p);
     let output_cost = model
         .output_price
-        .map(|p| (output_tokens as f64 / 1_000_000.0) * p);
+        .map(|p| (output_tokens as f64 / TOKENS_PER_PRICE_UNIT) * p);
 
     let total_cost = match (input_cost, output_cost) {
         (Some(i), Some(o)) => Some(i + o),
@@ -140,11 +140,11 @@ fn calculate_cost(
         Period::Once => ("once".to_string(), request_cost, None),
         Period::Daily => {
             let daily = request_cost;
-            let monthly = daily.map(|d| d * 30.0);
+            let monthly = daily.map(|d| d * DAYS_PER_MONTH);
             ("daily".to_string(), daily, monthly)
         }
         Period::Monthly => {
-            let monthly = request_cost.map(|c| c * 30.0);
+            let monthly = request_cost.map(|c| c * DAYS_PER_MONTH);
             ("monthly".to_string(), monthly, monthly)
         }
     };
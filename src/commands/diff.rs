@@ -0,0 +1,74 @@
+//! Diff command - compare two data snapshots.
+
+use crate::cache::Cache;
+use crate::error::{AppError, Result};
+use crate::history;
+use crate::output::OutputFormat;
+use crate::query::diff::diff_snapshots;
+use crate::query::{format_query_result, DEFAULT_PRECISION};
+use std::path::{Path, PathBuf};
+
+/// Run the diff command.
+///
+/// `from` and `to` are snapshot dates or directories of Parquet files. By
+/// default the snapshot before the latest one is compared against the
+/// current cache.
+pub fn run(from: Option<&str>, to: Option<&str>, format: OutputFormat) -> Result<()> {
+    let cache = Cache::new()?;
+    let cache_dir = cache.base_dir();
+
+    let (old_label, old_dir) = match from {
+        Some(spec) => resolve(cache_dir, spec)?,
+        None => previous_snapshot(cache_dir)?,
+    };
+    let (new_label, new_dir) = match to {
+        Some(spec) => resolve(cache_dir, spec)?,
+        None => ("the current cache".to_string(), cache_dir.to_path_buf()),
+    };
+
+    let result = diff_snapshots(&old_dir, &new_dir)?;
+    if result.is_empty() {
+        match format {
+            OutputFormat::Json => println!("[]"),
+            _ => println!("No changes between {} and {}.", old_label, new_label),
+        }
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        format_query_result(&result, format, DEFAULT_PRECISION)
+    );
+    Ok(())
+}
+
+/// Resolve a snapshot date or a directory, returning a label and the directory.
+fn resolve(cache_dir: &Path, spec: &str) -> Result<(String, PathBuf)> {
+    if let Ok(date) = history::parse_date(spec) {
+        let snapshot = history::snapshot_as_of(cache_dir, date)?;
+        return Ok((format!("snapshot {}", snapshot.date), snapshot.dir));
+    }
+
+    let path = PathBuf::from(spec);
+    if path.is_dir() {
+        Ok((path.display().to_string(), path))
+    } else {
+        Err(AppError::NotFound(format!(
+            "snapshot '{}': expected a date (YYYY-MM-DD) or a directory of Parquet files",
+            spec
+        )))
+    }
+}
+
+/// Get the snapshot before the latest one (the latest matches the cache).
+fn previous_snapshot(cache_dir: &Path) -> Result<(String, PathBuf)> {
+    let snapshots = history::list_snapshots(cache_dir)?;
+    match snapshots.len().checked_sub(2).map(|i| &snapshots[i]) {
+        Some(snapshot) => Ok((format!("snapshot {}", snapshot.date), snapshot.dir.clone())),
+        None => Err(AppError::NotFound(
+            "previous data snapshot. Every 'which-llm refresh' records one; diff needs two, \
+             or pass the snapshots to compare"
+                .into(),
+        )),
+    }
+}
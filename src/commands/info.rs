@@ -2,7 +2,7 @@
 
 use crate::cache::Cache;
 use crate::error::Result;
-use crate::remote::RemoteDataClient;
+use crate::remote::{DataSource, RemoteDataClient};
 
 /// Attribution text.
 const ATTRIBUTION: &str = "Data provided by Artificial Analysis (https://artificialanalysis.ai)";
@@ -10,9 +10,9 @@ const MODELS_DEV_ATTRIBUTION: &str = "Capability data from models.dev (https://m
 const METHODOLOGY_URL: &str = "https://artificialanalysis.ai/methodology";
 
 /// Run the info command.
-pub fn run() -> Result<()> {
+pub fn run(source: DataSource) -> Result<()> {
     let cache = Cache::new()?;
-    let remote = RemoteDataClient::new(cache.base_dir().to_path_buf())?;
+    let remote = RemoteDataClient::with_source(cache.base_dir().to_path_buf(), source)?;
 
     println!("which-llm v{}", env!("CARGO_PKG_VERSION"));
     println!();
@@ -20,9 +20,21 @@ pub fn run() -> Result<()> {
     // Show data source status
     println!("Data Source:");
     if let Some(manifest) = remote.get_local_manifest() {
-        println!("  Type: Hosted (GitHub Releases)");
+        println!("  Type: Hosted");
+        println!("  URL: {}", remote.source().base_url);
+        if let Some(tag) = remote.fetched_tag() {
+            println!("  Release: {}", tag);
+        }
         println!("  Generated: {}", manifest.generated_at);
         println!("  Version: {}", manifest.version);
+        println!(
+            "  Signature: {}",
+            if remote.is_manifest_signed() {
+                "verified"
+            } else {
+                "not verified"
+            }
+        );
         println!();
         println!("  Files:");
         for (name, info) in &manifest.files {
@@ -3,9 +3,12 @@
 pub mod cache;
 pub mod compare;
 pub mod cost;
+pub mod diff;
 pub mod info;
 pub mod profile;
 pub mod query;
 pub mod refresh;
 pub mod skill;
 pub mod tables;
+pub mod trend;
+pub mod watch;
@@ -5,7 +5,7 @@ use crate::error::Result;
 use dialoguer::{Input, Password};
 
 /// Create a new profile.
-pub fn create(name: &str, api_key: Option<&str>) -> Result<()> {
+pub fn create(name: &str, api_key: Option<&str>, data_url: Option<&str>) -> Result<()> {
     let mut config = Config::load()?;
 
     // Check if profile already exists
@@ -24,7 +24,13 @@ pub fn create(name: &str, api_key: Option<&str>) -> Result<()> {
     };
 
     // Create and save profile
-    config.set_profile(name.to_string(), Profile { api_key: key });
+    config.set_profile(
+        name.to_string(),
+        Profile {
+            api_key: key,
+            data_url: data_url.map(String::from),
+        },
+    );
 
     // Set as default if first profile
     if config.profiles.len() == 1 {
@@ -137,6 +143,9 @@ pub fn show(name: Option<&str>) -> Result<()> {
             ""
         }
     );
+    if let Some(data_url) = &profile.data_url {
+        println!("  Data URL: {}", data_url);
+    }
 
     Ok(())
 }
@@ -1,25 +1,44 @@
 //! Query command implementation.
 
 use crate::cache::Cache;
-use crate::error::Result;
+use crate::config::{Config, SavedQuery};
+use crate::error::{AppError, Result};
 use crate::output::OutputFormat;
-use crate::query::{format_query_result, format_tables_list, QueryExecutor};
+use crate::query::export::write_batches;
+use crate::query::params::placeholders;
+use crate::query::{format_query_result, script, QueryExecutor, QueryOptions, QueryResult};
+use crate::repl::Repl;
+use std::io::{IsTerminal, Read};
+use std::path::Path;
 
 /// Run the query command.
-pub fn run(sql: Option<&str>, tables: bool, format: OutputFormat) -> Result<()> {
+///
+/// The sandbox setting and views come from the config files; the remaining
+/// `options` come from the command line. `sql` may hold several `;`-separated
+/// statements; `params` bind `:name` placeholders in them.
+pub fn run(
+    sql: Option<&str>,
+    params: &[(String, String)],
+    format: OutputFormat,
+    precision: usize,
+    mut options: QueryOptions,
+    out: Option<&Path>,
+    explain: bool,
+) -> Result<()> {
     let cache = Cache::new()?;
-    let executor = QueryExecutor::new(cache.base_dir().to_path_buf());
-
-    if tables {
-        // List available tables
-        let table_info = executor.list_tables();
-        println!("{}", format_tables_list(&table_info));
-        return Ok(());
-    }
+    let config = Config::load()?;
+    options.sandbox = config.query.sandbox;
+    options.views = config.query_views()?;
+    let executor = QueryExecutor::with_options(cache.base_dir().to_path_buf(), options)?;
 
     // Execute SQL query
     let sql = match sql {
         Some(s) => s,
+        None if std::io::stdin().is_terminal() => {
+            // No query given on an interactive terminal: start the SQL shell
+            let history_path = Repl::default_history_path(cache.base_dir());
+            return Repl::new(executor, format, precision, history_path).run();
+        }
         None => {
             eprintln!("Error: No SQL query provided.");
             eprintln!();
@@ -27,13 +46,163 @@ pub fn run(sql: Option<&str>, tables: bool, format: OutputFormat) -> Result<()>
                 "Usage: which-llm query \"SELECT * FROM benchmarks WHERE intelligence > 40\""
             );
             eprintln!();
+            eprintln!("Run 'which-llm query' in a terminal for an interactive shell.");
             eprintln!("Use 'which-llm tables' to see available tables.");
             return Ok(());
         }
     };
 
-    let result = executor.execute(sql)?;
-    println!("{}", format_query_result(&result, format));
+    // Scripts run statement by statement; each statement gets the
+    // parameters it uses
+    let statements = script::split_statements(sql);
+    let (last, setup) = statements
+        .split_last()
+        .ok_or_else(|| AppError::Query("No SQL statements found.".into()))?;
+    script::check_params_used(sql, params)?;
+
+    if explain && !setup.is_empty() {
+        return Err(AppError::Query(
+            "--explain takes a single statement, not a script.".into(),
+        ));
+    }
+
+    for statement in setup {
+        let result =
+            executor.execute_with_params(statement, &script::params_for(statement, params))?;
+        if script::returns_rows(statement) {
+            print_result(&result, format, precision);
+        }
+    }
+
+    let params = script::params_for(last, params);
+
+    if explain {
+        // Show what would run without executing it
+        print!("{}", executor.explain(last, &params)?);
+        return Ok(());
+    }
+
+    if let Some(path) = out {
+        // Export straight from the Arrow batches to keep native types
+        let result = executor.execute_batches_with_params(last, &params)?;
+        let rows = write_batches(path, result.schema, &result.batches)?;
+        eprintln!("Wrote {} rows to {}", rows, path.display());
+        if let Some(truncation) = result.truncated {
+            eprintln!("Warning: {}", truncation);
+        }
+        return Ok(());
+    }
+
+    let result = executor.execute_with_params(last, &params)?;
+    if script::returns_rows(last) {
+        print_result(&result, format, precision);
+    }
+
+    Ok(())
+}
+
+/// Print a query result, warning on stderr if it was truncated.
+fn print_result(result: &QueryResult, format: OutputFormat, precision: usize) {
+    println!("{}", format_query_result(result, format, precision));
+    if let Some(truncation) = result.truncated {
+        eprintln!("Warning: {}", truncation);
+    }
+}
+
+/// Read a SQL script from a file, or from stdin when the path is `-`.
+pub fn read_script(path: &Path) -> Result<String> {
+    if path == Path::new("-") {
+        let mut sql = String::new();
+        std::io::stdin().read_to_string(&mut sql)?;
+        return Ok(sql);
+    }
+
+    std::fs::read_to_string(path)
+        .map_err(|e| AppError::Query(format!("Failed to read '{}': {}", path.display(), e)))
+}
+
+/// Get the SQL of a saved query.
+pub fn saved_sql(name: &str) -> Result<String> {
+    let config = Config::load()?;
+    config
+        .get_query(name)
+        .map(|q| q.sql.clone())
+        .ok_or_else(|| {
+            AppError::NotFound(format!(
+                "saved query '{}'. Run 'which-llm query list' to see saved queries.",
+                name
+            ))
+        })
+}
+
+/// Save a named query.
+pub fn save(name: &str, sql: &str, description: Option<&str>) -> Result<()> {
+    let mut config = Config::load()?;
+    let query = SavedQuery {
+        sql: sql.to_string(),
+        description: description.map(String::from),
+    };
+
+    let replaced = config.set_query(name.to_string(), query).is_some();
+    config.save()?;
+
+    let verb = if replaced { "Updated" } else { "Saved" };
+    println!("{} query '{}'.", verb, name);
+    let params = placeholders(sql);
+    if !params.is_empty() {
+        let flags: Vec<String> = params
+            .iter()
+            .map(|p| format!("--param {}=...", p))
+            .collect();
+        println!(
+            "Run it with: which-llm query run {} {}",
+            name,
+            flags.join(" ")
+        );
+    }
+
+    Ok(())
+}
+
+/// List saved queries.
+pub fn list() -> Result<()> {
+    let config = Config::load()?;
+
+    if config.queries.is_empty() {
+        println!("No saved queries. Run 'which-llm query save <name> \"<sql>\"' to create one.");
+        return Ok(());
+    }
+
+    println!("Saved queries:");
+    for (name, query) in &config.queries {
+        let params = placeholders(&query.sql);
+        let params = if params.is_empty() {
+            String::new()
+        } else {
+            let names: Vec<String> = params.iter().map(|p| format!(":{}", p)).collect();
+            format!(" ({})", names.join(", "))
+        };
+        println!("  - {}{}", name, params);
+        if let Some(description) = &query.description {
+            println!("      {}", description);
+        }
+        println!("      {}", query.sql);
+    }
+
+    Ok(())
+}
+
+/// Delete a saved query.
+pub fn delete(name: &str) -> Result<()> {
+    let mut config = Config::load()?;
+
+    if config.remove_query(name).is_none() {
+        eprintln!("Saved query '{}' not found.", name);
+        return Ok(());
+    }
+
+    config.save()?;
+    println!("Deleted query '{}'.", name);
 
     Ok(())
 }
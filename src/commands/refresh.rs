@@ -1,27 +1,40 @@
 //! Refresh command - fetch fresh data for all tables.
 
+use crate::cache::Cache;
 use crate::client::{Client, HostedDataClient};
 use crate::config::Config;
+use crate::crosswalk::{self, Overrides};
 use crate::error::Result;
+use crate::history;
+use crate::offline;
+use crate::progress::Progress;
+use crate::remote::DataSource;
+use chrono::{NaiveDate, Utc};
 
 /// Run the refresh command using hosted data client with API fallback.
-pub async fn run(quiet: bool, use_api: bool, profile: Option<&str>) -> Result<()> {
+pub async fn run(
+    quiet: bool,
+    use_api: bool,
+    profile: Option<&str>,
+    source: DataSource,
+) -> Result<()> {
+    let offline = source.offline;
     if use_api {
-        run_with_api(quiet, profile).await
+        run_with_api(quiet, profile, offline).await
     } else {
-        match run_with_hosted(quiet).await {
+        match run_with_hosted(quiet, source).await {
             Ok(()) => Ok(()),
             Err(e) => {
                 // Fallback to API if hosted data fails and API key is available
                 let config = Config::load()?;
-                if config.get_api_key(profile).is_ok() {
+                if config.get_api_key(profile).is_ok() && !offline {
                     if !quiet {
                         eprintln!(
                             "Warning: Could not fetch hosted data ({}). Falling back to API.",
                             e
                         );
                     }
-                    run_with_api(quiet, profile).await
+                    run_with_api(quiet, profile, offline).await
                 } else {
                     Err(e)
                 }
@@ -31,39 +44,31 @@ pub async fn run(quiet: bool, use_api: bool, profile: Option<&str>) -> Result<()
 }
 
 /// Refresh using hosted data.
-async fn run_with_hosted(quiet: bool) -> Result<()> {
-    let client = HostedDataClient::new()?;
+async fn run_with_hosted(quiet: bool, source: DataSource) -> Result<()> {
+    let client = HostedDataClient::with_source(source)?;
 
-    // Refresh benchmarks
-    if !quiet {
-        eprint!("Refreshing benchmarks... ");
-    }
-    let models = client.get_llm_models(true).await?;
+    // Download all changed files at once
     if !quiet {
-        eprintln!("done ({} models)", models.len());
+        eprintln!(
+            "Downloading data from {}...",
+            client.remote().source().base_url
+        );
     }
+    client
+        .remote()
+        .fetch_all_data(&Progress::for_refresh(quiet))
+        .await?;
 
-    // Refresh models.dev data
-    if !quiet {
-        eprint!("Refreshing models... ");
-    }
-    client.refresh_models().await?;
+    let models = client.get_llm_models(false).await?;
     if !quiet {
-        eprintln!("done");
+        eprintln!("Loaded {} models.", models.len());
     }
 
-    // Refresh media tables
-    if !quiet {
-        eprint!("Refreshing media tables... ");
-    }
-    let _ = client.get_text_to_image(true).await;
-    let _ = client.get_image_editing(true).await;
-    let _ = client.get_text_to_speech(true).await;
-    let _ = client.get_text_to_video(true).await;
-    let _ = client.get_image_to_video(true).await;
-    if !quiet {
-        eprintln!("done");
-    }
+    refresh_crosswalk(quiet)?;
+
+    // Snapshots are keyed by when the hosted data was generated
+    let manifest = client.remote().fetch_manifest().await?;
+    save_snapshot(quiet, history::snapshot_date(&manifest.generated_at)?)?;
 
     if !quiet {
         eprintln!();
@@ -74,7 +79,8 @@ async fn run_with_hosted(quiet: bool) -> Result<()> {
 }
 
 /// Refresh using API client.
-async fn run_with_api(quiet: bool, profile: Option<&str>) -> Result<()> {
+async fn run_with_api(quiet: bool, profile: Option<&str>, offline: bool) -> Result<()> {
+    offline::ensure_online(offline, "refresh from the APIs")?;
     let config = Config::load()?;
     let api_key = config.get_api_key(profile)?;
     let profile_name = profile
@@ -102,6 +108,8 @@ async fn run_with_api(quiet: bool, profile: Option<&str>) -> Result<()> {
         eprintln!("done");
     }
 
+    refresh_crosswalk(quiet)?;
+
     // Refresh media tables
     if !quiet {
         eprint!("Refreshing media tables... ");
@@ -115,6 +123,8 @@ async fn run_with_api(quiet: bool, profile: Option<&str>) -> Result<()> {
         eprintln!("done");
     }
 
+    save_snapshot(quiet, Utc::now().date_naive())?;
+
     if !quiet {
         eprintln!();
         eprintln!("All tables refreshed. Use 'which-llm tables' to see available data.");
@@ -122,3 +132,39 @@ async fn run_with_api(quiet: bool, profile: Option<&str>) -> Result<()> {
 
     Ok(())
 }
+
+/// Copy the refreshed tables into the history snapshot for `date`.
+fn save_snapshot(quiet: bool, date: NaiveDate) -> Result<()> {
+    let cache = Cache::new()?;
+    history::save_snapshot(cache.base_dir(), date)?;
+    if !quiet {
+        eprintln!("Saved snapshot {}", date);
+    }
+    Ok(())
+}
+
+/// Rebuild the crosswalk linking benchmarks to models.dev entries.
+///
+/// A failure only warns: the tables themselves were refreshed.
+fn refresh_crosswalk(quiet: bool) -> Result<()> {
+    let cache = Cache::new()?;
+
+    if !quiet {
+        eprint!("Linking benchmarks to models... ");
+    }
+    match Overrides::load().and_then(|overrides| crosswalk::rebuild(cache.base_dir(), &overrides)) {
+        Ok(count) => {
+            if !quiet {
+                eprintln!("done ({} links)", count);
+            }
+        }
+        Err(e) => {
+            if !quiet {
+                eprintln!("failed");
+            }
+            eprintln!("Warning: Could not build the crosswalk: {}", e);
+        }
+    }
+
+    Ok(())
+}
@@ -1,6 +1,7 @@
 //! Skill installation commands for AI coding tools.
 
 use crate::error::{AppError, Result};
+use crate::offline;
 use std::fs;
 use std::io::Cursor;
 use std::path::{Component, Path, PathBuf};
@@ -128,7 +129,8 @@ fn is_cache_fresh() -> Result<bool> {
 }
 
 /// Download skills.zip from GitHub releases.
-async fn download_skills_zip() -> Result<Vec<u8>> {
+async fn download_skills_zip(offline: bool) -> Result<Vec<u8>> {
+    offline::ensure_online(offline, "download skills.zip")?;
     let client = reqwest::Client::builder()
         .user_agent("which-llm-cli")
         .build()
@@ -156,18 +158,33 @@ async fn download_skills_zip() -> Result<Vec<u8>> {
 }
 
 /// Get skills.zip data, using cache if available and fresh.
-async fn get_skills_zip(force_refresh: bool) -> Result<Vec<u8>> {
+async fn get_skills_zip(force_refresh: bool, offline: bool) -> Result<Vec<u8>> {
     let zip_path = cached_zip_path()?;
 
-    // Use cache if fresh and not forcing refresh
-    if !force_refresh && is_cache_fresh()? {
+    // Use cache if fresh and not forcing refresh, or whenever offline
+    let use_cache = if offline {
+        if !zip_path.exists() {
+            return Err(AppError::Offline(format!(
+                "skills.zip is not cached (expected at {}). Run 'which-llm skill install' \
+                 with network access first.",
+                zip_path.display()
+            )));
+        }
+        if !is_cache_fresh()? {
+            eprintln!("Warning: offline mode, using a cached skills.zip older than 24 hours.");
+        }
+        true
+    } else {
+        !force_refresh && is_cache_fresh()?
+    };
+    if use_cache {
         return fs::read(&zip_path)
             .map_err(|e| AppError::Cache(format!("Failed to read cached skills.zip: {}", e)));
     }
 
     // Download fresh copy
     println!("Downloading skills from GitHub releases...");
-    let data = download_skills_zip().await?;
+    let data = download_skills_zip(offline).await?;
 
     // Cache the downloaded zip
     let cache_dir = cache_dir()?;
@@ -255,7 +272,13 @@ fn extract_skill_to(zip_data: &[u8], target_dir: &Path, dry_run: bool) -> Result
 }
 
 /// Install skill for a tool.
-pub async fn install(tool_name: &str, global: bool, force: bool, dry_run: bool) -> Result<()> {
+pub async fn install(
+    tool_name: &str,
+    global: bool,
+    force: bool,
+    dry_run: bool,
+    offline: bool,
+) -> Result<()> {
     let tool = Tool::parse(tool_name).ok_or_else(|| {
         AppError::Config(format!(
             "Unknown tool '{}'. Run 'which-llm skill list' to see supported tools.",
@@ -278,7 +301,7 @@ pub async fn install(tool_name: &str, global: bool, force: bool, dry_run: bool)
     }
 
     // Get skills.zip (from cache or download)
-    let zip_data = get_skills_zip(force).await?;
+    let zip_data = get_skills_zip(force, offline).await?;
 
     if dry_run {
         println!("Dry run: would install skill to {}", target_dir.display());
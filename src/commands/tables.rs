@@ -1,13 +1,20 @@
 //! Tables command - list available tables and their schemas.
 
 use crate::cache::Cache;
+use crate::config::Config;
 use crate::error::Result;
-use crate::query::{format_tables_list, QueryExecutor};
+use crate::query::{format_tables_list, QueryExecutor, QueryOptions};
 
 /// Run the tables command.
 pub fn run(table_name: Option<&str>) -> Result<()> {
     let cache = Cache::new()?;
-    let executor = QueryExecutor::new(cache.base_dir().to_path_buf());
+    let config = Config::load()?;
+    let options = QueryOptions {
+        sandbox: config.query.sandbox,
+        views: config.query_views()?,
+        ..Default::default()
+    };
+    let executor = QueryExecutor::with_options(cache.base_dir().to_path_buf(), options)?;
 
     let table_info = executor.list_tables();
 
@@ -15,10 +22,10 @@ pub fn run(table_name: Option<&str>) -> Result<()> {
         // Show details for a specific table
         if let Some(info) = table_info.iter().find(|t| t.name == name) {
             println!("Table: {}", info.name);
-            println!(
-                "Status: {}",
-                if info.exists { "cached" } else { "not cached" }
-            );
+            println!("Status: {}", info.status());
+            if let Some(sql) = &info.view_sql {
+                println!("Definition: {}", sql);
+            }
             if let Some(columns) = &info.schema {
                 println!();
                 println!("Columns:");
@@ -31,7 +38,7 @@ pub fn run(table_name: Option<&str>) -> Result<()> {
                     );
                 }
             }
-            if !info.exists {
+            if !info.exists && info.view_sql.is_none() {
                 println!();
                 println!("Run 'which-llm refresh' to cache this table.");
             }
@@ -0,0 +1,303 @@
+//! Trend command.
+//!
+//! Shows how a model's prices, scores and speed changed across the stored
+//! data snapshots, as a table plus a sparkline per field.
+
+use crate::client::HostedDataClient;
+use crate::error::{AppError, Result};
+use crate::models::LlmModel;
+use crate::output::OutputFormat;
+use crate::query::{format_query_result, CellValue, QueryResult, DEFAULT_PRECISION};
+use crate::schema::TREND_FIELDS;
+use crate::utils::find_models_by_names;
+use chrono::NaiveDate;
+use std::collections::HashSet;
+
+/// Sparkline bars, lowest to highest.
+const SPARK_BARS: &[char] = &['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Get a model's value for one of [`TREND_FIELDS`].
+fn field_value(model: &LlmModel, field: &str) -> Option<f64> {
+    match field {
+        "intelligence" => model.intelligence,
+        "coding" => model.coding,
+        "math" => model.math,
+        "mmlu_pro" => model.mmlu_pro,
+        "gpqa" => model.gpqa,
+        "hle" => model.hle,
+        "livecodebench" => model.livecodebench,
+        "scicode" => model.scicode,
+        "math_500" => model.math_500,
+        "aime" => model.aime,
+        "input_price" => model.input_price,
+        "output_price" => model.output_price,
+        "price" => model.price,
+        "tps" => model.tps,
+        "latency" => model.latency,
+        _ => None,
+    }
+}
+
+/// One model's values across the snapshots it appears in.
+#[derive(Debug, Clone, PartialEq)]
+struct Series {
+    slug: String,
+    name: String,
+    /// Snapshot date and the value of each requested field.
+    points: Vec<(NaiveDate, Vec<Option<f64>>)>,
+}
+
+impl Series {
+    /// Values of the `i`th field, oldest first.
+    fn values(&self, i: usize) -> Vec<Option<f64>> {
+        self.points.iter().map(|(_, values)| values[i]).collect()
+    }
+}
+
+/// Render values as a Unicode sparkline, scaled between their minimum and
+/// maximum. Missing values are shown as spaces.
+pub fn sparkline(values: &[Option<f64>]) -> String {
+    let present = values.iter().flatten();
+    let min = present.clone().copied().fold(f64::INFINITY, f64::min);
+    let max = present.copied().fold(f64::NEG_INFINITY, f64::max);
+    let top = SPARK_BARS.len() - 1;
+
+    values
+        .iter()
+        .map(|value| match value {
+            None => ' ',
+            Some(_) if max <= min => SPARK_BARS[top / 2],
+            Some(v) => SPARK_BARS[(((v - min) / (max - min)) * top as f64).round() as usize],
+        })
+        .collect()
+}
+
+/// Build the series of the models matching `searches`.
+///
+/// Models are matched against every snapshot, so models that have since
+/// been removed can still be charted.
+fn build_series(
+    history: &[(NaiveDate, Vec<LlmModel>)],
+    searches: &[String],
+    fields: &[String],
+) -> Vec<Series> {
+    // Latest version of every model seen in any snapshot
+    let mut seen = HashSet::new();
+    let known: Vec<LlmModel> = history
+        .iter()
+        .rev()
+        .flat_map(|(_, models)| models)
+        .filter(|m| seen.insert(m.id.as_str()))
+        .cloned()
+        .collect();
+
+    find_models_by_names(&known, searches)
+        .into_iter()
+        .map(|model| Series {
+            slug: model.slug.clone(),
+            name: model.display_name().to_string(),
+            points: history
+                .iter()
+                .filter_map(|(date, models)| {
+                    let m = models.iter().find(|m| m.id == model.id)?;
+                    Some((*date, fields.iter().map(|f| field_value(m, f)).collect()))
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+/// Build a result table with a `date` column, an optional `model` column
+/// and one column per field.
+fn series_result(series: &[&Series], fields: &[String], with_model: bool) -> QueryResult {
+    let mut columns = vec!["date".to_string()];
+    if with_model {
+        columns.push("model".to_string());
+    }
+    columns.extend(fields.iter().cloned());
+
+    let rows = series
+        .iter()
+        .flat_map(|s| {
+            s.points.iter().map(move |(date, values)| {
+                let mut row = vec![CellValue::Date(*date)];
+                if with_model {
+                    row.push(CellValue::Text(s.slug.clone()));
+                }
+                row.extend(
+                    values
+                        .iter()
+                        .map(|v| v.map_or(CellValue::Null, CellValue::Float)),
+                );
+                row
+            })
+        })
+        .collect();
+
+    QueryResult {
+        columns,
+        rows,
+        truncated: None,
+    }
+}
+
+/// Run the trend command.
+///
+/// JSON, CSV and plain output hold one row per model and snapshot; the
+/// human-readable formats show a table and sparklines per model.
+pub fn run(searches: &[String], fields: &[String], format: OutputFormat) -> Result<()> {
+    let client = HostedDataClient::new()?;
+    let history = client.get_llm_models_history()?;
+    if history.is_empty() {
+        return Err(AppError::NotFound(
+            "data snapshots. Every 'which-llm refresh' records one".into(),
+        ));
+    }
+
+    let series = build_series(&history, searches, fields);
+    if series.is_empty() {
+        return Err(AppError::NotFound(format!(
+            "No models found matching: {}",
+            searches.join(", ")
+        )));
+    }
+
+    if matches!(
+        format,
+        OutputFormat::Json | OutputFormat::Csv | OutputFormat::Plain
+    ) {
+        let all: Vec<&Series> = series.iter().collect();
+        let result = series_result(&all, fields, true);
+        println!(
+            "{}",
+            format_query_result(&result, format, DEFAULT_PRECISION)
+        );
+        return Ok(());
+    }
+
+    let width = fields.iter().map(|f| f.len()).max().unwrap_or(0);
+    for (i, s) in series.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        println!("{} ({})", s.name, s.slug);
+        println!();
+        let result = series_result(&[s], fields, false);
+        println!(
+            "{}",
+            format_query_result(&result, format, DEFAULT_PRECISION)
+        );
+        println!();
+
+        for (j, field) in fields.iter().enumerate() {
+            let values = s.values(j);
+            let present: Vec<f64> = values.iter().flatten().copied().collect();
+            let range = match (present.first(), present.last()) {
+                (Some(first), Some(last)) => format!(
+                    "{} → {}",
+                    CellValue::Float(*first).display(DEFAULT_PRECISION),
+                    CellValue::Float(*last).display(DEFAULT_PRECISION)
+                ),
+                _ => "no data".to_string(),
+            };
+            println!(
+                "{:<width$}  {}  {}",
+                field,
+                sparkline(&values),
+                range,
+                width = width
+            );
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn model(id: &str, name: &str, price: Option<f64>, tps: f64) -> LlmModel {
+        LlmModel {
+            id: id.to_string(),
+            name: name.to_string(),
+            slug: name.to_lowercase(),
+            creator: "OpenAI".to_string(),
+            price,
+            tps: Some(tps),
+            ..Default::default()
+        }
+    }
+
+    fn date(s: &str) -> NaiveDate {
+        crate::history::parse_date(s).unwrap()
+    }
+
+    #[test]
+    fn test_every_trend_field_has_a_value() {
+        let model = LlmModel {
+            intelligence: Some(1.0),
+            coding: Some(1.0),
+            math: Some(1.0),
+            mmlu_pro: Some(1.0),
+            gpqa: Some(1.0),
+            hle: Some(1.0),
+            livecodebench: Some(1.0),
+            scicode: Some(1.0),
+            math_500: Some(1.0),
+            aime: Some(1.0),
+            input_price: Some(1.0),
+            output_price: Some(1.0),
+            price: Some(1.0),
+            tps: Some(1.0),
+            latency: Some(1.0),
+            ..Default::default()
+        };
+        for field in TREND_FIELDS {
+            assert!(field_value(&model, field).is_some(), "{field}");
+        }
+    }
+
+    #[test]
+    fn test_sparkline() {
+        assert_eq!(sparkline(&[Some(1.0), Some(2.0), Some(3.0)]), "▁▅█");
+        assert_eq!(sparkline(&[Some(5.0), None, Some(1.0)]), "█ ▁");
+        assert_eq!(sparkline(&[Some(2.0), Some(2.0)]), "▄▄");
+        assert_eq!(sparkline(&[None]), " ");
+        assert_eq!(sparkline(&[]), "");
+    }
+
+    #[test]
+    fn test_build_series() {
+        let history = vec![
+            (
+                date("2026-08-01"),
+                vec![
+                    model("1", "GPT-4o", Some(4.0), 80.0),
+                    model("2", "Old", None, 10.0),
+                ],
+            ),
+            (date("2026-09-01"), vec![model("1", "GPT-4o", None, 90.0)]),
+            (
+                date("2026-10-01"),
+                vec![model("1", "GPT-4o", Some(2.5), 120.0)],
+            ),
+        ];
+        let fields = vec!["price".to_string(), "tps".to_string()];
+
+        let series = build_series(&history, &["gpt-4o".to_string()], &fields);
+        assert_eq!(series.len(), 1);
+        assert_eq!(series[0].slug, "gpt-4o");
+        assert_eq!(series[0].points.len(), 3);
+        assert_eq!(series[0].values(0), vec![Some(4.0), None, Some(2.5)]);
+        assert_eq!(sparkline(&series[0].values(1)), "▁▃█");
+
+        // Removed models are still matched
+        let series = build_series(&history, &["old".to_string()], &fields);
+        assert_eq!(series[0].points.len(), 1);
+
+        let result = series_result(&[&series[0]], &fields, true);
+        assert_eq!(result.columns, vec!["date", "model", "price", "tps"]);
+        assert_eq!(result.rows[0][2], CellValue::Null);
+    }
+}
@@ -0,0 +1,468 @@
+//! Watchlist command.
+//!
+//! Watched models and their thresholds are stored under `[watch]` in
+//! config.toml. `watch check` compares them with the cached data and fails
+//! when a threshold is crossed or a watched model is removed or deprecated,
+//! so it can gate a cron job or CI step after `which-llm refresh`.
+
+use crate::client::HostedDataClient;
+use crate::config::{Config, WatchRule};
+use crate::crosswalk::deprecated_providers;
+use crate::error::{AppError, Result};
+use crate::models::LlmModel;
+use crate::offline;
+use crate::remote::DataSource;
+use crate::utils::filter_models_by_name;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// Time allowed to connect to the webhook.
+const WEBHOOK_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Time allowed for the whole webhook call, so a hanging endpoint can't
+/// stall a cron job.
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Whether a threshold is an upper or a lower limit.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Bound {
+    Max,
+    Min,
+}
+
+/// A threshold on one model field.
+struct Threshold {
+    field: &'static str,
+    bound: Bound,
+    limit: fn(&WatchRule) -> Option<f64>,
+    value: fn(&LlmModel) -> Option<f64>,
+}
+
+/// Thresholds in the order they are checked and listed.
+const THRESHOLDS: &[Threshold] = &[
+    Threshold {
+        field: "price",
+        bound: Bound::Max,
+        limit: |r| r.max_price,
+        value: |m| m.price,
+    },
+    Threshold {
+        field: "input_price",
+        bound: Bound::Max,
+        limit: |r| r.max_input_price,
+        value: |m| m.input_price,
+    },
+    Threshold {
+        field: "output_price",
+        bound: Bound::Max,
+        limit: |r| r.max_output_price,
+        value: |m| m.output_price,
+    },
+    Threshold {
+        field: "intelligence",
+        bound: Bound::Min,
+        limit: |r| r.min_intelligence,
+        value: |m| m.intelligence,
+    },
+    Threshold {
+        field: "coding",
+        bound: Bound::Min,
+        limit: |r| r.min_coding,
+        value: |m| m.coding,
+    },
+    Threshold {
+        field: "tps",
+        bound: Bound::Min,
+        limit: |r| r.min_tps,
+        value: |m| m.tps,
+    },
+    Threshold {
+        field: "latency",
+        bound: Bound::Max,
+        limit: |r| r.max_latency,
+        value: |m| m.latency,
+    },
+];
+
+/// Why a watched model was flagged.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ViolationKind {
+    /// A value crossed its threshold.
+    Threshold,
+    /// The model is no longer in the benchmarks data.
+    Removed,
+    /// A provider marks the model as deprecated on models.dev.
+    Deprecated,
+}
+
+/// A watch rule that no longer holds.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Violation {
+    /// Model slug.
+    pub model: String,
+    pub kind: ViolationKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub field: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<f64>,
+    pub message: String,
+}
+
+/// Result of `watch check`, printed with `--json` and sent to the webhook.
+#[derive(Debug, Serialize)]
+struct Report<'a> {
+    checked: usize,
+    violations: &'a [Violation],
+}
+
+/// Describe a rule's thresholds, e.g. `price <= 2, intelligence >= 50`.
+fn describe(rule: &WatchRule) -> String {
+    let limits: Vec<String> = THRESHOLDS
+        .iter()
+        .filter_map(|t| {
+            let op = if t.bound == Bound::Max { "<=" } else { ">=" };
+            (t.limit)(rule).map(|limit| format!("{} {} {}", t.field, op, limit))
+        })
+        .collect();
+    if limits.is_empty() {
+        "removal and deprecation only".to_string()
+    } else {
+        limits.join(", ")
+    }
+}
+
+/// Check the watch rules against the current models.
+///
+/// `deprecated` maps model slugs to the providers deprecating them. Missing
+/// values never violate a threshold.
+pub fn evaluate(
+    watches: &BTreeMap<String, WatchRule>,
+    models: &[LlmModel],
+    deprecated: &BTreeMap<String, Vec<String>>,
+) -> Vec<Violation> {
+    let mut violations = Vec::new();
+
+    for (slug, rule) in watches {
+        let Some(model) = models.iter().find(|m| &m.slug == slug) else {
+            violations.push(Violation {
+                model: slug.clone(),
+                kind: ViolationKind::Removed,
+                field: None,
+                limit: None,
+                value: None,
+                message: "no longer in the benchmarks data".to_string(),
+            });
+            continue;
+        };
+
+        for threshold in THRESHOLDS {
+            let (Some(limit), Some(value)) = ((threshold.limit)(rule), (threshold.value)(model))
+            else {
+                continue;
+            };
+            let message = match threshold.bound {
+                Bound::Max if value > limit => {
+                    format!(
+                        "{} {} is above the maximum {}",
+                        threshold.field, value, limit
+                    )
+                }
+                Bound::Min if value < limit => {
+                    format!(
+                        "{} {} is below the minimum {}",
+                        threshold.field, value, limit
+                    )
+                }
+                _ => continue,
+            };
+            violations.push(Violation {
+                model: slug.clone(),
+                kind: ViolationKind::Threshold,
+                field: Some(threshold.field.to_string()),
+                limit: Some(limit),
+                value: Some(value),
+                message,
+            });
+        }
+
+        if let Some(providers) = deprecated.get(slug) {
+            violations.push(Violation {
+                model: slug.clone(),
+                kind: ViolationKind::Deprecated,
+                field: None,
+                limit: None,
+                value: None,
+                message: format!("deprecated by {}", providers.join(", ")),
+            });
+        }
+    }
+
+    violations
+}
+
+/// Resolve a search term to a single model slug.
+fn resolve_slug(models: &[LlmModel], search: &str) -> Result<String> {
+    if let Some(model) = models.iter().find(|m| m.slug == search) {
+        return Ok(model.slug.clone());
+    }
+
+    match filter_models_by_name(models, search).as_slice() {
+        [] => Err(AppError::NotFound(format!(
+            "No models found matching: {}",
+            search
+        ))),
+        [model] => Ok(model.slug.clone()),
+        matches => {
+            let slugs: Vec<&str> = matches.iter().map(|m| m.slug.as_str()).collect();
+            Err(AppError::Config(format!(
+                "'{}' matches {} models ({}). Watch one of them by its slug.",
+                search,
+                matches.len(),
+                slugs.join(", ")
+            )))
+        }
+    }
+}
+
+/// Watch a model, replacing any earlier thresholds for it.
+pub async fn add(search: &str, rule: WatchRule, source: DataSource) -> Result<()> {
+    let client = HostedDataClient::with_source(source)?;
+    let models = client.get_llm_models(false).await?;
+    let slug = resolve_slug(&models, search)?;
+
+    let mut config = Config::load()?;
+    let description = describe(&rule);
+    let replaced = config.set_watch(slug.clone(), rule).is_some();
+    config.save()?;
+
+    let verb = if replaced {
+        "Updated watch on"
+    } else {
+        "Watching"
+    };
+    println!("{} '{}': {}.", verb, slug, description);
+    Ok(())
+}
+
+/// List watched models.
+pub fn list() -> Result<()> {
+    let config = Config::load()?;
+
+    if config.watch.models.is_empty() {
+        println!("No watched models. Run 'which-llm watch add <model>' to add one.");
+        return Ok(());
+    }
+
+    println!("Watched models:");
+    for (slug, rule) in &config.watch.models {
+        println!("  - {}: {}", slug, describe(rule));
+    }
+    if let Some(webhook) = &config.watch.webhook {
+        println!();
+        println!("Webhook: {}", webhook);
+    }
+
+    Ok(())
+}
+
+/// Stop watching a model.
+pub fn remove(slug: &str) -> Result<()> {
+    let mut config = Config::load()?;
+
+    if config.remove_watch(slug).is_none() {
+        eprintln!("Model '{}' is not watched.", slug);
+        return Ok(());
+    }
+
+    config.save()?;
+    println!("Stopped watching '{}'.", slug);
+
+    Ok(())
+}
+
+/// Check the watch rules against the cached data.
+///
+/// Violations are printed (or emitted as JSON), POSTed to the webhook if one
+/// is configured, and make the command fail.
+pub async fn check(json: bool, webhook: Option<&str>, source: DataSource) -> Result<()> {
+    let config = Config::load()?;
+    let watches = &config.watch.models;
+    if watches.is_empty() {
+        if json {
+            let report = Report {
+                checked: 0,
+                violations: &[],
+            };
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        } else {
+            println!("No watched models. Run 'which-llm watch add <model>' to add one.");
+        }
+        return Ok(());
+    }
+
+    let offline = source.offline;
+    let client = HostedDataClient::with_source(source)?;
+    let models = client.get_llm_models(false).await?;
+    let deprecated = deprecated_providers(client.cache().base_dir())?;
+    let violations = evaluate(watches, &models, &deprecated);
+
+    let report = Report {
+        checked: watches.len(),
+        violations: &violations,
+    };
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else if violations.is_empty() {
+        println!(
+            "All {} watched models are within their limits.",
+            watches.len()
+        );
+    } else {
+        println!("Watchlist violations:");
+        for violation in &violations {
+            println!("  - {}: {}", violation.model, violation.message);
+        }
+    }
+
+    if violations.is_empty() {
+        return Ok(());
+    }
+
+    if let Some(url) = webhook.or(config.watch.webhook.as_deref()) {
+        if let Err(e) = post_webhook(url, &report, offline).await {
+            eprintln!("Warning: {}", e);
+        }
+    }
+
+    Err(AppError::WatchViolations(violations.len()))
+}
+
+/// POST a report to the webhook.
+async fn post_webhook(url: &str, report: &Report<'_>, offline: bool) -> Result<()> {
+    offline::ensure_online(offline, "call the webhook")?;
+    let response = reqwest::Client::builder()
+        .user_agent(format!("which-llm/{}", env!("CARGO_PKG_VERSION")))
+        .connect_timeout(WEBHOOK_CONNECT_TIMEOUT)
+        .timeout(WEBHOOK_TIMEOUT)
+        .build()?
+        .post(url)
+        .json(report)
+        .send()
+        .await
+        .map_err(|e| AppError::Network(format!("Failed to call webhook: {}", e)))?;
+
+    if !response.status().is_success() {
+        return Err(AppError::Network(format!(
+            "Webhook returned HTTP {}",
+            response.status()
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn model(slug: &str, price: f64, intelligence: f64) -> LlmModel {
+        LlmModel {
+            id: slug.to_string(),
+            name: slug.to_uppercase(),
+            slug: slug.to_string(),
+            creator: "OpenAI".to_string(),
+            price: Some(price),
+            intelligence: Some(intelligence),
+            ..Default::default()
+        }
+    }
+
+    fn watches(rules: &[(&str, WatchRule)]) -> BTreeMap<String, WatchRule> {
+        rules
+            .iter()
+            .map(|(slug, rule)| (slug.to_string(), rule.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_evaluate_thresholds() {
+        let models = vec![model("gpt-4o", 2.5, 48.0), model("gpt-4o-mini", 0.3, 40.0)];
+        let rule = WatchRule {
+            max_price: Some(2.0),
+            min_intelligence: Some(50.0),
+            min_tps: Some(100.0),
+            ..Default::default()
+        };
+        let lenient = WatchRule {
+            max_price: Some(1.0),
+            ..Default::default()
+        };
+
+        let violations = evaluate(
+            &watches(&[("gpt-4o", rule), ("gpt-4o-mini", lenient)]),
+            &models,
+            &BTreeMap::new(),
+        );
+        assert_eq!(violations.len(), 2);
+        assert_eq!(violations[0].field.as_deref(), Some("price"));
+        assert_eq!(violations[0].message, "price 2.5 is above the maximum 2");
+        assert_eq!(violations[1].field.as_deref(), Some("intelligence"));
+        assert_eq!(violations[1].kind, ViolationKind::Threshold);
+    }
+
+    #[test]
+    fn test_evaluate_removed_and_deprecated() {
+        let models = vec![model("gpt-4o", 2.5, 48.0)];
+        let deprecated = BTreeMap::from([(
+            "gpt-4o".to_string(),
+            vec!["azure".to_string(), "openai".to_string()],
+        )]);
+
+        let violations = evaluate(
+            &watches(&[
+                ("gpt-4o", WatchRule::default()),
+                ("gpt-3.5-turbo", WatchRule::default()),
+            ]),
+            &models,
+            &deprecated,
+        );
+        assert_eq!(violations.len(), 2);
+        assert_eq!(violations[0].kind, ViolationKind::Removed);
+        assert_eq!(violations[0].model, "gpt-3.5-turbo");
+        assert_eq!(violations[1].kind, ViolationKind::Deprecated);
+        assert_eq!(violations[1].message, "deprecated by azure, openai");
+
+        let json = serde_json::to_value(&violations[0]).unwrap();
+        assert_eq!(json["kind"], "removed");
+        assert!(json.get("limit").is_none());
+    }
+
+    #[test]
+    fn test_resolve_slug() {
+        let models = vec![model("gpt-4o", 2.5, 48.0), model("gpt-4o-mini", 0.3, 40.0)];
+        assert_eq!(resolve_slug(&models, "gpt-4o").unwrap(), "gpt-4o");
+        assert_eq!(resolve_slug(&models, "MINI").unwrap(), "gpt-4o-mini");
+        assert!(resolve_slug(&models, "gpt-4")
+            .unwrap_err()
+            .to_string()
+            .contains("matches 2 models (gpt-4o, gpt-4o-mini)"));
+        assert!(resolve_slug(&models, "claude").is_err());
+    }
+
+    #[test]
+    fn test_describe() {
+        let rule = WatchRule {
+            max_price: Some(2.0),
+            min_intelligence: Some(50.0),
+            ..Default::default()
+        };
+        assert_eq!(describe(&rule), "price <= 2, intelligence >= 50");
+        assert_eq!(
+            describe(&WatchRule::default()),
+            "removal and deprecation only"
+        );
+    }
+}
@@ -2,13 +2,121 @@
 
 use crate::error::{AppError, Result};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
 
 /// Profile configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Profile {
     pub api_key: String,
+    /// Base URL of the hosted data for this profile (overrides `[data]`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data_url: Option<String>,
+}
+
+/// Settings for the `query` command.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueryConfig {
+    /// Only allow read-only SQL over the cached tables (default: true).
+    #[serde(default = "default_true")]
+    pub sandbox: bool,
+}
+
+impl Default for QueryConfig {
+    fn default() -> Self {
+        Self { sandbox: true }
+    }
+}
+
+/// Where hosted data is downloaded from.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DataConfig {
+    /// Base URL holding `manifest.json` and the Parquet files: an
+    /// `http(s)://` mirror or a `file://` directory (default: GitHub Releases).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// GitHub release tag to pin, e.g. `data/2026-10-01` (default: `data/latest`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    /// Seconds to wait for a connection (default: 10).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub connect_timeout: Option<u64>,
+    /// Seconds to wait for more data before a download stalls (default: 30).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub read_timeout: Option<u64>,
+    /// Times a failed download is retried (default: 3).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retries: Option<u32>,
+    /// minisign public keys trusted to sign `manifest.json`, besides the
+    /// built-in release key.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub trusted_keys: Vec<String>,
+    /// Refuse data whose manifest isn't signed by a trusted key.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub require_signature: bool,
+}
+
+impl DataConfig {
+    fn is_empty(&self) -> bool {
+        self.url.is_none()
+            && self.tag.is_none()
+            && self.connect_timeout.is_none()
+            && self.read_timeout.is_none()
+            && self.retries.is_none()
+            && self.trusted_keys.is_empty()
+            && !self.require_signature
+    }
+}
+
+/// A saved query, run with `which-llm query run <name>`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedQuery {
+    /// SQL with optional `:name` placeholders.
+    pub sql: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// Thresholds for a watched model, checked by `which-llm watch check`.
+///
+/// Prices are per 1M tokens (USD), speed in tokens/s and latency in seconds.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WatchRule {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_price: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_input_price: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_output_price: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_intelligence: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_coding: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_tps: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_latency: Option<f64>,
+}
+
+/// Settings for the `watch` command.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WatchConfig {
+    /// URL that `watch check` POSTs violations to as JSON.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub webhook: Option<String>,
+    /// Watched models (model slug -> thresholds).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub models: BTreeMap<String, WatchRule>,
+}
+
+impl WatchConfig {
+    fn is_empty(&self) -> bool {
+        self.webhook.is_none() && self.models.is_empty()
+    }
+}
+
+fn default_true() -> bool {
+    true
 }
 
 /// Application configuration.
@@ -18,6 +126,44 @@ pub struct Config {
     pub default_profile: Option<String>,
     #[serde(default)]
     pub profiles: HashMap<String, Profile>,
+    #[serde(default)]
+    pub query: QueryConfig,
+    #[serde(default, skip_serializing_if = "DataConfig::is_empty")]
+    pub data: DataConfig,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub queries: BTreeMap<String, SavedQuery>,
+    /// User-defined views (name -> SELECT), registered next to the built-in tables.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub views: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "WatchConfig::is_empty")]
+    pub watch: WatchConfig,
+}
+
+/// Project configuration file, looked up from the current directory upwards.
+pub const PROJECT_CONFIG_FILE: &str = "which-llm.toml";
+
+/// Project-level configuration, shared by checking it into a repository.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProjectConfig {
+    /// Views that add to (and override) the ones in the user config.
+    #[serde(default)]
+    pub views: BTreeMap<String, String>,
+}
+
+impl ProjectConfig {
+    /// Find the nearest project config in `dir` or one of its ancestors.
+    pub fn find(dir: &Path) -> Result<Option<(PathBuf, Self)>> {
+        for ancestor in dir.ancestors() {
+            let path = ancestor.join(PROJECT_CONFIG_FILE);
+            if path.is_file() {
+                let content = std::fs::read_to_string(&path)?;
+                let config: ProjectConfig = toml::from_str(&content)
+                    .map_err(|e| AppError::Config(format!("Invalid {}: {}", path.display(), e)))?;
+                return Ok(Some((path, config)));
+            }
+        }
+        Ok(None)
+    }
 }
 
 impl Config {
@@ -86,6 +232,52 @@ impl Config {
         self.default_profile = Some(name);
     }
 
+    /// Get the configured data base URL: the given (or default) profile's
+    /// `data_url`, falling back to `url` under `[data]`.
+    pub fn data_url(&self, profile_name: Option<&str>) -> Option<&str> {
+        profile_name
+            .or(self.default_profile.as_deref())
+            .and_then(|name| self.profiles.get(name))
+            .and_then(|profile| profile.data_url.as_deref())
+            .or(self.data.url.as_deref())
+    }
+
+    /// Get a saved query by name.
+    pub fn get_query(&self, name: &str) -> Option<&SavedQuery> {
+        self.queries.get(name)
+    }
+
+    /// Add or replace a saved query, returning the previous one.
+    pub fn set_query(&mut self, name: String, query: SavedQuery) -> Option<SavedQuery> {
+        self.queries.insert(name, query)
+    }
+
+    /// Remove a saved query.
+    pub fn remove_query(&mut self, name: &str) -> Option<SavedQuery> {
+        self.queries.remove(name)
+    }
+
+    /// Add or replace the watch rule for a model slug, returning the previous one.
+    pub fn set_watch(&mut self, slug: String, rule: WatchRule) -> Option<WatchRule> {
+        self.watch.models.insert(slug, rule)
+    }
+
+    /// Stop watching a model.
+    pub fn remove_watch(&mut self, slug: &str) -> Option<WatchRule> {
+        self.watch.models.remove(slug)
+    }
+
+    /// Get the user-defined views from this config merged with the project
+    /// config found from the current directory (project views win).
+    pub fn query_views(&self) -> Result<BTreeMap<String, String>> {
+        let mut views = self.views.clone();
+        let cwd = std::env::current_dir()?;
+        if let Some((_, project)) = ProjectConfig::find(&cwd)? {
+            views.extend(project.views);
+        }
+        Ok(views)
+    }
+
     /// Get the API key for the given profile, or the default profile.
     /// Environment variable ARTIFICIAL_ANALYSIS_API_KEY takes precedence.
     pub fn get_api_key(&self, profile_name: Option<&str>) -> Result<String> {
@@ -119,6 +311,16 @@ mod tests {
         let config = Config::default();
         assert!(config.default_profile.is_none());
         assert!(config.profiles.is_empty());
+        assert!(config.query.sandbox);
+    }
+
+    #[test]
+    fn test_config_query_sandbox() {
+        let config: Config = toml::from_str("[query]\n").unwrap();
+        assert!(config.query.sandbox);
+
+        let config: Config = toml::from_str("[query]\nsandbox = false\n").unwrap();
+        assert!(!config.query.sandbox);
     }
 
     #[test]
@@ -129,6 +331,7 @@ mod tests {
             "test".into(),
             Profile {
                 api_key: "key123".into(),
+                data_url: None,
             },
         );
 
@@ -141,4 +344,99 @@ mod tests {
         config.remove_profile("test");
         assert!(config.get_profile("test").is_none());
     }
+
+    #[test]
+    fn test_config_data_url() {
+        let config: Config = toml::from_str(
+            r#"
+            default_profile = "ci"
+
+            [data]
+            url = "https://mirror.example.com/which-llm"
+
+            [profiles.ci]
+            api_key = "key123"
+            data_url = "file:///srv/which-llm"
+
+            [profiles.home]
+            api_key = "key456"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.data_url(None), Some("file:///srv/which-llm"));
+        assert_eq!(
+            config.data_url(Some("home")),
+            Some("https://mirror.example.com/which-llm")
+        );
+        assert_eq!(Config::default().data_url(None), None);
+    }
+
+    #[test]
+    fn test_config_saved_queries_roundtrip() {
+        let mut config = Config::default();
+        config.set_query(
+            "cheap-coders".into(),
+            SavedQuery {
+                sql: "SELECT name FROM benchmarks WHERE coding > :min_coding".into(),
+                description: None,
+            },
+        );
+
+        let content = toml::to_string_pretty(&config).unwrap();
+        assert!(content.contains("[queries.cheap-coders]"));
+
+        let loaded: Config = toml::from_str(&content).unwrap();
+        assert_eq!(
+            loaded.get_query("cheap-coders"),
+            config.get_query("cheap-coders")
+        );
+
+        config.remove_query("cheap-coders");
+        assert!(config.get_query("cheap-coders").is_none());
+    }
+
+    #[test]
+    fn test_config_watch_roundtrip() {
+        let mut config = Config::default();
+        let content = toml::to_string_pretty(&config).unwrap();
+        assert!(!content.contains("[watch"));
+
+        config.watch.webhook = Some("https://example.com/hook".into());
+        config.set_watch(
+            "gpt-4o".into(),
+            WatchRule {
+                max_price: Some(2.0),
+                min_intelligence: Some(50.0),
+                ..Default::default()
+            },
+        );
+
+        let content = toml::to_string_pretty(&config).unwrap();
+        assert!(content.contains("[watch.models.gpt-4o]"));
+        assert!(!content.contains("max_latency"));
+
+        let loaded: Config = toml::from_str(&content).unwrap();
+        assert_eq!(loaded.watch.models, config.watch.models);
+        assert_eq!(loaded.watch.webhook, config.watch.webhook);
+
+        assert!(config.remove_watch("gpt-4o").is_some());
+        assert!(config.watch.models.is_empty());
+    }
+
+    #[test]
+    fn test_project_config_views() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let nested = temp_dir.path().join("src").join("deep");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::write(
+            temp_dir.path().join(PROJECT_CONFIG_FILE),
+            "[views]\nfrontier = \"SELECT * FROM benchmarks WHERE intelligence > 60\"\n",
+        )
+        .unwrap();
+
+        let (path, project) = ProjectConfig::find(&nested).unwrap().unwrap();
+        assert_eq!(path, temp_dir.path().join(PROJECT_CONFIG_FILE));
+        assert!(project.views["frontier"].contains("intelligence > 60"));
+    }
 }
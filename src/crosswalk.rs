@@ -0,0 +1,615 @@
+//! Crosswalk linking Artificial Analysis benchmarks to models.dev entries.
+//!
+//! The two sources share no key: AA identifies a model by `id`/`slug`
+//! (`claude-4-5-sonnet`), models.dev by `provider_id`/`model_id`
+//! (`anthropic`/`claude-sonnet-4-5-20250929`). The crosswalk is rebuilt on
+//! every refresh by comparing normalized names, then the user's overrides
+//! from `crosswalk_overrides.toml` are applied on top.
+
+use crate::config::Config;
+use crate::error::{AppError, Result};
+use crate::parquet::write_crosswalk_parquet;
+use crate::schema::{BENCHMARKS, CROSSWALK, MODELS};
+use duckdb::Connection;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Overrides file name, stored next to `config.toml`.
+pub const OVERRIDES_FILE: &str = "crosswalk_overrides.toml";
+
+/// Minimum token overlap (Jaccard index) for a fuzzy link.
+const MIN_FUZZY_SIMILARITY: f64 = 0.75;
+
+/// A link between a benchmarked model and a models.dev entry.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CrosswalkRow {
+    pub aa_id: String,
+    pub aa_slug: String,
+    pub provider_id: String,
+    pub model_id: String,
+    /// 1.0 for exact matches and overrides, lower for looser matches.
+    pub confidence: f64,
+    /// How the link was made: exact, normalized, reordered, name, fuzzy or override.
+    pub method: String,
+}
+
+/// A benchmarked model from Artificial Analysis.
+#[derive(Debug, Clone)]
+pub struct AaEntry {
+    pub id: String,
+    pub slug: String,
+    pub name: String,
+    pub creator_slug: Option<String>,
+}
+
+/// A model entry from models.dev.
+#[derive(Debug, Clone)]
+pub struct ModelsDevEntry {
+    pub provider_id: String,
+    pub model_id: String,
+    pub model_name: String,
+}
+
+/// User corrections to the automatic links.
+///
+/// ```toml
+/// [[link]]
+/// slug = "gpt-4o"
+/// provider_id = "azure"
+/// model_id = "gpt-4o-deployment"
+///
+/// [[unlink]]
+/// slug = "gpt-4o"
+/// provider_id = "openrouter"
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Overrides {
+    #[serde(default, rename = "link")]
+    pub links: Vec<Link>,
+    #[serde(default, rename = "unlink")]
+    pub unlinks: Vec<Unlink>,
+}
+
+/// Force a link between a benchmarked model and a models.dev entry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Link {
+    pub slug: String,
+    pub provider_id: String,
+    pub model_id: String,
+}
+
+/// Remove the automatic links of a benchmarked model, optionally only those
+/// to one provider or model.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Unlink {
+    pub slug: String,
+    #[serde(default)]
+    pub provider_id: Option<String>,
+    #[serde(default)]
+    pub model_id: Option<String>,
+}
+
+impl Unlink {
+    fn matches(&self, row: &CrosswalkRow) -> bool {
+        row.aa_slug == self.slug
+            && !matches!(&self.provider_id, Some(p) if *p != row.provider_id)
+            && !matches!(&self.model_id, Some(m) if *m != row.model_id)
+    }
+}
+
+impl Overrides {
+    /// Get the overrides file path.
+    pub fn path() -> Result<PathBuf> {
+        Ok(Config::config_path()?.with_file_name(OVERRIDES_FILE))
+    }
+
+    /// Load the overrides from the default path.
+    pub fn load() -> Result<Self> {
+        Self::load_from(&Self::path()?)
+    }
+
+    /// Load the overrides from a file, or none if it does not exist.
+    pub fn load_from(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(path)?;
+        toml::from_str(&content)
+            .map_err(|e| AppError::Config(format!("Invalid {}: {}", path.display(), e)))
+    }
+
+    /// Apply unlinks, then links, to the automatic links.
+    ///
+    /// Links naming a slug that is not in the benchmarks are ignored.
+    pub fn apply(&self, rows: &mut Vec<CrosswalkRow>, aa: &[AaEntry]) {
+        rows.retain(|row| !self.unlinks.iter().any(|u| u.matches(row)));
+
+        for link in &self.links {
+            let Some(entry) = aa.iter().find(|e| e.slug == link.slug) else {
+                continue;
+            };
+            rows.retain(|r| {
+                !(r.aa_id == entry.id
+                    && r.provider_id == link.provider_id
+                    && r.model_id == link.model_id)
+            });
+            rows.push(CrosswalkRow {
+                aa_id: entry.id.clone(),
+                aa_slug: entry.slug.clone(),
+                provider_id: link.provider_id.clone(),
+                model_id: link.model_id.clone(),
+                confidence: 1.0,
+                method: "override".to_string(),
+            });
+        }
+    }
+}
+
+/// Rebuild `crosswalk.parquet` from the cached benchmarks and models tables.
+///
+/// Returns the number of links written.
+pub fn rebuild(cache_dir: &Path, overrides: &Overrides) -> Result<usize> {
+    let conn = Connection::open_in_memory()
+        .map_err(|e| AppError::Cache(format!("DuckDB error: {}", e)))?;
+
+    let aa = read_entries(
+        &conn,
+        &format!(
+            "SELECT id, slug, name, creator_slug FROM read_parquet('{}')",
+            parquet_path(cache_dir, BENCHMARKS.parquet_file)
+        ),
+        |row| {
+            Ok(AaEntry {
+                id: row.get(0)?,
+                slug: row.get(1)?,
+                name: row.get(2)?,
+                creator_slug: row.get(3)?,
+            })
+        },
+    )?;
+    let models_dev = read_entries(
+        &conn,
+        &format!(
+            "SELECT provider_id, model_id, model_name FROM read_parquet('{}')",
+            parquet_path(cache_dir, MODELS.parquet_file)
+        ),
+        |row| {
+            Ok(ModelsDevEntry {
+                provider_id: row.get(0)?,
+                model_id: row.get(1)?,
+                model_name: row.get(2)?,
+            })
+        },
+    )?;
+
+    let mut rows = link(&aa, &models_dev);
+    overrides.apply(&mut rows, &aa);
+
+    write_crosswalk_parquet(&rows, &cache_dir.join(CROSSWALK.parquet_file))?;
+    Ok(rows.len())
+}
+
+/// Get the providers whose linked models.dev entries are marked
+/// `deprecated`, keyed by benchmark slug.
+///
+/// Returns an empty map when the crosswalk or models.dev data isn't cached.
+pub fn deprecated_providers(cache_dir: &Path) -> Result<BTreeMap<String, Vec<String>>> {
+    if !cache_dir.join(CROSSWALK.parquet_file).exists()
+        || !cache_dir.join(MODELS.parquet_file).exists()
+    {
+        return Ok(BTreeMap::new());
+    }
+
+    let conn = Connection::open_in_memory()
+        .map_err(|e| AppError::Cache(format!("DuckDB error: {}", e)))?;
+    let rows = read_entries(
+        &conn,
+        &format!(
+            "SELECT DISTINCT c.aa_slug, c.provider_id \
+             FROM read_parquet('{}') c JOIN read_parquet('{}') m \
+             ON c.provider_id = m.provider_id AND c.model_id = m.model_id \
+             WHERE m.status = 'deprecated' ORDER BY 1, 2",
+            parquet_path(cache_dir, CROSSWALK.parquet_file),
+            parquet_path(cache_dir, MODELS.parquet_file)
+        ),
+        |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)),
+    )?;
+
+    let mut deprecated: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for (slug, provider_id) in rows {
+        deprecated.entry(slug).or_default().push(provider_id);
+    }
+    Ok(deprecated)
+}
+
+fn parquet_path(cache_dir: &Path, file: &str) -> String {
+    cache_dir
+        .join(file)
+        .to_string_lossy()
+        .replace('\\', "/")
+        .replace('\'', "''")
+}
+
+fn read_entries<T>(
+    conn: &Connection,
+    sql: &str,
+    f: impl FnMut(&duckdb::Row<'_>) -> duckdb::Result<T>,
+) -> Result<Vec<T>> {
+    let mut stmt = conn
+        .prepare(sql)
+        .map_err(|e| AppError::Cache(format!("DuckDB error: {}", e)))?;
+    let rows = stmt
+        .query_map([], f)
+        .map_err(|e| AppError::Cache(format!("DuckDB error: {}", e)))?;
+    rows.collect::<duckdb::Result<Vec<T>>>()
+        .map_err(|e| AppError::Cache(format!("DuckDB error: {}", e)))
+}
+
+/// Link every benchmarked model to the models.dev entries that match it.
+///
+/// Only the best-scoring entries are kept for each (model, provider) pair, so
+/// a provider's dated snapshots don't crowd out its canonical alias.
+pub fn link(aa: &[AaEntry], models_dev: &[ModelsDevEntry]) -> Vec<CrosswalkRow> {
+    let models_dev: Vec<(&ModelsDevEntry, Vec<String>, Vec<String>)> = models_dev
+        .iter()
+        .map(|m| {
+            (
+                m,
+                drop_dates(&tokens(model_key(&m.model_id))),
+                drop_dates(&tokens(&m.model_name)),
+            )
+        })
+        .collect();
+
+    let mut rows = Vec::new();
+    for entry in aa {
+        let slug_tokens = tokens(&entry.slug);
+        let name_tokens = tokens(&entry.name);
+        let creator = entry
+            .creator_slug
+            .as_deref()
+            .map(tokens)
+            .unwrap_or_default();
+
+        let mut best: BTreeMap<&str, (f64, Vec<CrosswalkRow>)> = BTreeMap::new();
+        for (m, id_tokens, model_name_tokens) in &models_dev {
+            let id_tokens = strip_creator(id_tokens, &creator);
+            let model_name_tokens = strip_creator(model_name_tokens, &creator);
+
+            let (confidence, method) = if model_key(&m.model_id).eq_ignore_ascii_case(&entry.slug) {
+                (1.0, "exact")
+            } else if id_tokens == slug_tokens.as_slice() {
+                (0.95, "normalized")
+            } else if same_tokens(id_tokens, &slug_tokens) {
+                (0.9, "reordered")
+            } else if !name_tokens.is_empty() && same_tokens(model_name_tokens, &name_tokens) {
+                (0.85, "name")
+            } else {
+                match fuzzy_similarity(id_tokens, &slug_tokens) {
+                    Some(j) if j >= MIN_FUZZY_SIMILARITY => (0.75 * j, "fuzzy"),
+                    _ => continue,
+                }
+            };
+
+            let row = CrosswalkRow {
+                aa_id: entry.id.clone(),
+                aa_slug: entry.slug.clone(),
+                provider_id: m.provider_id.clone(),
+                model_id: m.model_id.clone(),
+                confidence,
+                method: method.to_string(),
+            };
+            let slot = best
+                .entry(m.provider_id.as_str())
+                .or_insert((confidence, Vec::new()));
+            if confidence > slot.0 {
+                *slot = (confidence, vec![row]);
+            } else if confidence == slot.0 {
+                slot.1.push(row);
+            }
+        }
+        rows.extend(best.into_values().flat_map(|(_, rows)| rows));
+    }
+
+    rows
+}
+
+/// Strip aggregator prefixes (`openai/gpt-4o`) and variant suffixes
+/// (`gpt-4o:free`) from a models.dev model id.
+fn model_key(model_id: &str) -> &str {
+    let id = model_id.rsplit('/').next().unwrap_or(model_id);
+    id.split(':').next().unwrap_or(id)
+}
+
+/// Split a model name into lowercase word and number tokens.
+///
+/// Letters are split from the digits that follow them, so `llama3.1`,
+/// `llama-3-1` and `Llama 3.1` all become `[llama, 3, 1]`.
+fn tokens(s: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    for part in s
+        .to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|p| !p.is_empty())
+    {
+        let mut current = String::new();
+        for c in part.chars() {
+            let starts_number = c.is_ascii_digit()
+                && current
+                    .chars()
+                    .last()
+                    .is_some_and(|p| p.is_ascii_alphabetic())
+                && !current.chars().any(|p| p.is_ascii_digit());
+            if starts_number {
+                tokens.push(std::mem::take(&mut current));
+            }
+            current.push(c);
+        }
+        tokens.push(current);
+    }
+    tokens
+}
+
+/// Drop snapshot dates (`20240620`, `2024-08-06`) and `latest` tags.
+fn drop_dates(tokens: &[String]) -> Vec<String> {
+    let is_number = |t: &str, len: usize| t.len() == len && t.chars().all(|c| c.is_ascii_digit());
+
+    let mut kept = Vec::new();
+    let mut i = 0;
+    while i < tokens.len() {
+        let t = tokens[i].as_str();
+        if is_number(t, 8) && t.starts_with("20") {
+            i += 1;
+        } else if is_number(t, 4)
+            && t.starts_with("20")
+            && tokens.get(i + 1).is_some_and(|n| is_number(n, 2))
+        {
+            i += 1;
+            while tokens.get(i).is_some_and(|n| is_number(n, 2)) {
+                i += 1;
+            }
+        } else {
+            if t != "latest" {
+                kept.push(tokens[i].clone());
+            }
+            i += 1;
+        }
+    }
+    kept
+}
+
+/// Drop a leading creator name (`meta-llama-3.1-70b`, `anthropic.claude-3`).
+fn strip_creator<'a>(tokens: &'a [String], creator: &[String]) -> &'a [String] {
+    match creator.first() {
+        Some(c) if tokens.len() > 1 && tokens[0] == *c => &tokens[1..],
+        _ => tokens,
+    }
+}
+
+/// Whether two token lists contain the same tokens in any order.
+fn same_tokens(a: &[String], b: &[String]) -> bool {
+    let mut a = a.to_vec();
+    let mut b = b.to_vec();
+    a.sort_unstable();
+    b.sort_unstable();
+    a == b
+}
+
+/// Jaccard index of the token sets, or `None` if the numbers differ
+/// (`gpt-4` must never link to `gpt-5`).
+fn fuzzy_similarity(a: &[String], b: &[String]) -> Option<f64> {
+    let numbers = |tokens: &[String]| {
+        let mut n: Vec<&String> = tokens
+            .iter()
+            .filter(|t| t.chars().any(|c| c.is_ascii_digit()))
+            .collect();
+        n.sort_unstable();
+        n
+    };
+    if numbers(a) != numbers(b) {
+        return None;
+    }
+
+    let shared = a.iter().filter(|t| b.contains(t)).count();
+    let total = a.len() + b.len() - shared;
+    if total == 0 {
+        return None;
+    }
+    Some(shared as f64 / total as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn aa(id: &str, slug: &str, name: &str, creator: &str) -> AaEntry {
+        AaEntry {
+            id: id.to_string(),
+            slug: slug.to_string(),
+            name: name.to_string(),
+            creator_slug: Some(creator.to_string()),
+        }
+    }
+
+    fn md(provider_id: &str, model_id: &str, model_name: &str) -> ModelsDevEntry {
+        ModelsDevEntry {
+            provider_id: provider_id.to_string(),
+            model_id: model_id.to_string(),
+            model_name: model_name.to_string(),
+        }
+    }
+
+    fn find<'a>(rows: &'a [CrosswalkRow], provider_id: &str) -> Vec<&'a CrosswalkRow> {
+        rows.iter()
+            .filter(|r| r.provider_id == provider_id)
+            .collect()
+    }
+
+    #[test]
+    fn test_tokens() {
+        assert_eq!(tokens("Llama3.1-70B"), vec!["llama", "3", "1", "70b"]);
+        assert_eq!(tokens("gpt-4o"), vec!["gpt", "4o"]);
+        assert_eq!(
+            drop_dates(&tokens("claude-3-5-sonnet-20240620")),
+            vec!["claude", "3", "5", "sonnet"]
+        );
+        assert_eq!(drop_dates(&tokens("gpt-4o-2024-08-06")), vec!["gpt", "4o"]);
+        assert_eq!(model_key("openai/gpt-4o:free"), "gpt-4o");
+    }
+
+    #[test]
+    fn test_link_methods() {
+        let aa = vec![
+            aa("1", "gpt-4o", "GPT-4o", "openai"),
+            aa("2", "claude-4-5-sonnet", "Claude 4.5 Sonnet", "anthropic"),
+            aa("3", "llama-3-1-instruct-70b", "Llama 3.1 70B", "meta"),
+        ];
+        let models_dev = vec![
+            md("openai", "gpt-4o", "GPT-4o"),
+            md("openai", "gpt-4o-2024-08-06", "GPT-4o (2024-08-06)"),
+            md("openrouter", "openai/gpt-4o", "GPT-4o"),
+            md(
+                "anthropic",
+                "claude-sonnet-4-5-20250929",
+                "Claude Sonnet 4.5",
+            ),
+            md(
+                "together",
+                "meta-llama/Meta-Llama-3.1-70B-Instruct",
+                "Llama 3.1 70B",
+            ),
+            md("openai", "gpt-5", "GPT-5"),
+        ];
+
+        let rows = link(&aa, &models_dev);
+
+        // The canonical alias beats the dated snapshot
+        let openai: Vec<_> = find(&rows, "openai");
+        assert_eq!(openai.len(), 1);
+        assert_eq!(openai[0].model_id, "gpt-4o");
+        assert_eq!(openai[0].method, "exact");
+
+        assert_eq!(find(&rows, "openrouter")[0].confidence, 1.0);
+
+        let anthropic = find(&rows, "anthropic");
+        assert_eq!(anthropic[0].aa_id, "2");
+        assert_eq!(anthropic[0].method, "reordered");
+
+        let together = find(&rows, "together");
+        assert_eq!(together[0].aa_id, "3");
+        assert_eq!(together[0].method, "reordered");
+
+        assert!(rows.iter().all(|r| r.model_id != "gpt-5"));
+    }
+
+    #[test]
+    fn test_fuzzy_requires_same_numbers() {
+        let a = tokens("gemini-2-5-flash-preview");
+        assert!(fuzzy_similarity(&a, &tokens("gemini-2-5-flash")).unwrap() >= 0.75);
+        assert!(fuzzy_similarity(&a, &tokens("gemini-2-0-flash-preview")).is_none());
+    }
+
+    #[test]
+    fn test_overrides() {
+        let overrides: Overrides = toml::from_str(
+            r#"
+            [[link]]
+            slug = "gpt-4o"
+            provider_id = "azure"
+            model_id = "my-gpt-4o"
+
+            [[link]]
+            slug = "not-benchmarked"
+            provider_id = "azure"
+            model_id = "other"
+
+            [[unlink]]
+            slug = "gpt-4o"
+            provider_id = "openrouter"
+            "#,
+        )
+        .unwrap();
+
+        let aa = vec![aa("1", "gpt-4o", "GPT-4o", "openai")];
+        let mut rows = link(
+            &aa,
+            &[
+                md("openai", "gpt-4o", "GPT-4o"),
+                md("openrouter", "openai/gpt-4o", "GPT-4o"),
+            ],
+        );
+        overrides.apply(&mut rows, &aa);
+
+        assert_eq!(rows.len(), 2);
+        assert!(find(&rows, "openrouter").is_empty());
+        let azure = find(&rows, "azure");
+        assert_eq!(azure[0].model_id, "my-gpt-4o");
+        assert_eq!(azure[0].method, "override");
+    }
+
+    #[test]
+    fn test_load_missing_overrides() {
+        let temp_dir = TempDir::new().unwrap();
+        let overrides = Overrides::load_from(&temp_dir.path().join(OVERRIDES_FILE)).unwrap();
+        assert!(overrides.links.is_empty() && overrides.unlinks.is_empty());
+
+        let path = temp_dir.path().join("bad.toml");
+        std::fs::write(&path, "[[link]]\nslug = 1").unwrap();
+        assert!(Overrides::load_from(&path).is_err());
+    }
+
+    #[test]
+    fn test_rebuild() {
+        let temp_dir = TempDir::new().unwrap();
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(&format!(
+            "COPY (SELECT * FROM (VALUES ('1', 'gpt-4o', 'GPT-4o', 'openai')) \
+             t(id, slug, name, creator_slug)) TO '{}' (FORMAT PARQUET);
+             COPY (SELECT * FROM (VALUES ('openai', 'gpt-4o', 'GPT-4o')) \
+             t(provider_id, model_id, model_name)) TO '{}' (FORMAT PARQUET);",
+            parquet_path(temp_dir.path(), "benchmarks.parquet"),
+            parquet_path(temp_dir.path(), "models.parquet"),
+        ))
+        .unwrap();
+
+        let count = rebuild(temp_dir.path(), &Overrides::default()).unwrap();
+        assert_eq!(count, 1);
+
+        let method: String = conn
+            .query_row(
+                &format!(
+                    "SELECT method FROM read_parquet('{}')",
+                    parquet_path(temp_dir.path(), "crosswalk.parquet")
+                ),
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(method, "exact");
+    }
+
+    #[test]
+    fn test_deprecated_providers() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(deprecated_providers(temp_dir.path()).unwrap().is_empty());
+
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(&format!(
+            "COPY (SELECT * FROM (VALUES ('1', 'gpt-4o', 'GPT-4o', 'openai')) \
+             t(id, slug, name, creator_slug)) TO '{}' (FORMAT PARQUET);
+             COPY (SELECT * FROM (VALUES ('openai', 'gpt-4o', 'GPT-4o', 'deprecated'), \
+             ('azure', 'gpt-4o', 'GPT-4o', NULL)) \
+             t(provider_id, model_id, model_name, status)) TO '{}' (FORMAT PARQUET);",
+            parquet_path(temp_dir.path(), "benchmarks.parquet"),
+            parquet_path(temp_dir.path(), "models.parquet"),
+        ))
+        .unwrap();
+        rebuild(temp_dir.path(), &Overrides::default()).unwrap();
+
+        let deprecated = deprecated_providers(temp_dir.path()).unwrap();
+        assert_eq!(deprecated.len(), 1);
+        assert_eq!(deprecated["gpt-4o"], vec!["openai".to_string()]);
+    }
+}
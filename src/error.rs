@@ -40,6 +40,29 @@ pub enum AppError {
     #[error("{0}")]
     Query(String),
 
+    #[error(
+        "Unknown {kind} '{name}'.{} Use 'which-llm tables' to see available tables and columns.\nError: {details}",
+        did_you_mean(.suggestions)
+    )]
+    UnknownName {
+        kind: &'static str,
+        name: String,
+        suggestions: Vec<String>,
+        details: String,
+    },
+
+    #[error("Blocked by query sandbox: {0}. Set 'sandbox = false' under [query] in config.toml to allow it.")]
+    Sandbox(String),
+
+    #[error("Offline mode: {0}")]
+    Offline(String),
+
+    #[error("Signature check failed: {0}")]
+    Signature(String),
+
+    #[error("Watchlist check failed: {0} violation(s)")]
+    WatchViolations(usize),
+
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 
@@ -56,4 +79,60 @@ pub enum AppError {
     Http(#[from] reqwest::Error),
 }
 
+impl AppError {
+    /// Structured form of the error for `--json` output.
+    pub fn to_json(&self) -> serde_json::Value {
+        let mut error = serde_json::json!({ "message": self.to_string() });
+        if let Self::UnknownName {
+            kind,
+            name,
+            suggestions,
+            ..
+        } = self
+        {
+            error["kind"] = format!("unknown_{}", kind).into();
+            error["name"] = name.clone().into();
+            error["suggestions"] = suggestions.clone().into();
+        }
+        serde_json::json!({ "error": error })
+    }
+}
+
+/// Format the "did you mean" part of an error message.
+fn did_you_mean(suggestions: &[String]) -> String {
+    if suggestions.is_empty() {
+        return String::new();
+    }
+    let quoted: Vec<String> = suggestions.iter().map(|s| format!("'{}'", s)).collect();
+    format!(" Did you mean {}?", quoted.join(", "))
+}
+
 pub type Result<T> = std::result::Result<T, AppError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unknown_name_message_and_json() {
+        let err = AppError::UnknownName {
+            kind: "column",
+            name: "price_input".into(),
+            suggestions: vec!["input_price".into(), "price".into()],
+            details: "Binder Error".into(),
+        };
+        assert!(err
+            .to_string()
+            .starts_with("Unknown column 'price_input'. Did you mean 'input_price', 'price'?"));
+
+        let json = err.to_json();
+        assert_eq!(json["error"]["kind"], "unknown_column");
+        assert_eq!(json["error"]["suggestions"][0], "input_price");
+    }
+
+    #[test]
+    fn test_to_json_plain_error() {
+        let json = AppError::Query("boom".into()).to_json();
+        assert_eq!(json, serde_json::json!({ "error": { "message": "boom" } }));
+    }
+}
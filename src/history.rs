@@ -0,0 +1,163 @@
+//! Dated snapshots of the cached tables.
+//!
+//! Every successful refresh copies the cached Parquet files into
+//! `history/<YYYY-MM-DD>/`, keyed by the manifest's `generated_at` (or the
+//! refresh date for data fetched from the APIs). Snapshots back the
+//! `<table>_history` tables and the `--as-of` option.
+
+use crate::error::{AppError, Result};
+use crate::schema::ALL_TABLES;
+use chrono::{DateTime, NaiveDate, Utc};
+use std::path::{Path, PathBuf};
+
+/// Directory under the cache dir holding the snapshots.
+pub const HISTORY_DIR: &str = "history";
+
+/// Suffix of the tables exposing every snapshot of a table.
+pub const HISTORY_SUFFIX: &str = "_history";
+
+/// Date format of snapshot directory names.
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// A snapshot directory and the date it is keyed by.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Snapshot {
+    pub date: NaiveDate,
+    pub dir: PathBuf,
+}
+
+/// Parse a `YYYY-MM-DD` command-line date.
+pub fn parse_date(s: &str) -> std::result::Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(s, DATE_FORMAT)
+        .map_err(|_| format!("expected a date like 2026-09-01, got '{}'", s))
+}
+
+/// Get the snapshot date for a manifest's `generated_at` timestamp.
+pub fn snapshot_date(generated_at: &str) -> Result<NaiveDate> {
+    DateTime::parse_from_rfc3339(generated_at)
+        .map(|dt| dt.with_timezone(&Utc).date_naive())
+        .or_else(|_| NaiveDate::parse_from_str(generated_at.get(..10).unwrap_or(""), DATE_FORMAT))
+        .map_err(|_| AppError::Cache(format!("Invalid generated_at '{}'", generated_at)))
+}
+
+/// Copy the cached tables into the snapshot for `date`, replacing any
+/// earlier snapshot from the same day.
+///
+/// Returns the snapshot directory.
+pub fn save_snapshot(cache_dir: &Path, date: NaiveDate) -> Result<PathBuf> {
+    let dir = cache_dir
+        .join(HISTORY_DIR)
+        .join(date.format(DATE_FORMAT).to_string());
+    std::fs::create_dir_all(&dir)?;
+
+    for table in ALL_TABLES {
+        let source = cache_dir.join(table.parquet_file);
+        let target = dir.join(table.parquet_file);
+        if source.exists() {
+            std::fs::copy(&source, &target)?;
+        } else if target.exists() {
+            std::fs::remove_file(&target)?;
+        }
+    }
+
+    Ok(dir)
+}
+
+/// List the snapshots, oldest first.
+///
+/// Entries that are not `YYYY-MM-DD` directories are ignored.
+pub fn list_snapshots(cache_dir: &Path) -> Result<Vec<Snapshot>> {
+    let history_dir = cache_dir.join(HISTORY_DIR);
+    if !history_dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut snapshots = Vec::new();
+    for entry in std::fs::read_dir(&history_dir)? {
+        let dir = entry?.path();
+        let date = dir
+            .file_name()
+            .and_then(|n| n.to_str())
+            .and_then(|n| NaiveDate::parse_from_str(n, DATE_FORMAT).ok());
+        if let (Some(date), true) = (date, dir.is_dir()) {
+            snapshots.push(Snapshot { date, dir });
+        }
+    }
+    snapshots.sort_by_key(|s| s.date);
+    Ok(snapshots)
+}
+
+/// Find the snapshot in effect on `as_of`: the latest one taken on or
+/// before that date.
+pub fn snapshot_as_of(cache_dir: &Path, as_of: NaiveDate) -> Result<Snapshot> {
+    let snapshots = list_snapshots(cache_dir)?;
+    if let Some(snapshot) = snapshots.iter().rev().find(|s| s.date <= as_of) {
+        return Ok(snapshot.clone());
+    }
+
+    let hint = match snapshots.first() {
+        Some(first) => format!("The oldest snapshot is from {}.", first.date),
+        None => "Snapshots are recorded by 'which-llm refresh'.".to_string(),
+    };
+    Err(AppError::NotFound(format!(
+        "no data snapshot on or before {}. {}",
+        as_of, hint
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn date(s: &str) -> NaiveDate {
+        parse_date(s).unwrap()
+    }
+
+    #[test]
+    fn test_snapshot_date() {
+        assert_eq!(
+            snapshot_date("2026-09-01T23:30:00-02:00").unwrap(),
+            date("2026-09-02")
+        );
+        assert_eq!(snapshot_date("2026-09-01").unwrap(), date("2026-09-01"));
+        assert!(snapshot_date("yesterday").is_err());
+        assert!(parse_date("09/01/2026").is_err());
+    }
+
+    #[test]
+    fn test_save_and_resolve_snapshots() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache_dir = temp_dir.path();
+
+        let err = snapshot_as_of(cache_dir, date("2026-09-01")).unwrap_err();
+        assert!(err.to_string().contains("which-llm refresh"));
+
+        std::fs::write(cache_dir.join("benchmarks.parquet"), "v1").unwrap();
+        save_snapshot(cache_dir, date("2026-08-01")).unwrap();
+        std::fs::write(cache_dir.join("benchmarks.parquet"), "v2").unwrap();
+        std::fs::write(cache_dir.join("models.parquet"), "v2").unwrap();
+        save_snapshot(cache_dir, date("2026-09-01")).unwrap();
+        std::fs::create_dir_all(cache_dir.join(HISTORY_DIR).join("notes")).unwrap();
+
+        let snapshots = list_snapshots(cache_dir).unwrap();
+        assert_eq!(snapshots.len(), 2);
+        assert!(!snapshots[0].dir.join("models.parquet").exists());
+
+        let snapshot = snapshot_as_of(cache_dir, date("2026-08-15")).unwrap();
+        assert_eq!(snapshot.date, date("2026-08-01"));
+        assert_eq!(
+            std::fs::read_to_string(snapshot.dir.join("benchmarks.parquet")).unwrap(),
+            "v1"
+        );
+        assert_eq!(
+            snapshot_as_of(cache_dir, date("2026-10-01")).unwrap().date,
+            date("2026-09-01")
+        );
+
+        let err = snapshot_as_of(cache_dir, date("2026-07-01")).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("oldest snapshot is from 2026-08-01"));
+    }
+}
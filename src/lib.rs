@@ -5,13 +5,19 @@ pub mod cli;
 pub mod client;
 pub mod commands;
 pub mod config;
+pub mod crosswalk;
 pub mod error;
+pub mod history;
 pub mod models;
+pub mod offline;
 pub mod output;
 pub mod parquet;
+pub mod progress;
 pub mod query;
 pub mod remote;
+pub mod repl;
 pub mod schema;
+pub mod signature;
 pub mod sources;
 pub mod utils;
 
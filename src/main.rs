@@ -1,57 +1,132 @@
 //! which-llm - Query AI model benchmarks from the terminal.
 
 use clap::Parser;
+use std::path::Path;
+use std::time::Duration;
 use which_llm::{
-    cli::{get_output_format, CacheCommands, Cli, Commands, ProfileCommands, SkillCommands},
+    cli::{
+        get_output_format, CacheCommands, Cli, Commands, ProfileCommands, QueryCommands,
+        SkillCommands, WatchCommands,
+    },
     client::HostedDataClient,
     commands,
+    config::{Config, WatchRule},
     error::Result,
+    query::QueryOptions,
+    remote::DataSource,
 };
 
 #[tokio::main]
 async fn main() {
-    if let Err(e) = run().await {
-        eprintln!("Error: {}", e);
+    let cli = Cli::parse();
+    // `query --json` reports errors as JSON on stdout so callers can parse them
+    let json_errors = matches!(cli.command, Commands::Query { json: true, .. });
+
+    if let Err(e) = run(&cli).await {
+        if json_errors {
+            println!("{}", e.to_json());
+        } else {
+            eprintln!("Error: {}", e);
+        }
         std::process::exit(1);
     }
 }
 
-async fn run() -> Result<()> {
-    let cli = Cli::parse();
-
+async fn run(cli: &Cli) -> Result<()> {
     match &cli.command {
         // Query command - primary interface
         Commands::Query {
+            command:
+                Some(QueryCommands::Save {
+                    name,
+                    sql,
+                    description,
+                }),
+            ..
+        } => commands::query::save(name, sql, description.as_deref()),
+        Commands::Query {
+            command: Some(QueryCommands::List),
+            ..
+        } => commands::query::list(),
+        Commands::Query {
+            command: Some(QueryCommands::Delete { name }),
+            ..
+        } => commands::query::delete(name),
+        Commands::Query {
+            command,
             sql,
+            file,
             json,
             csv,
             table,
             plain,
+            precision,
+            timeout,
+            max_rows,
+            memory_limit,
+            out,
+            explain,
+            as_of,
+            params,
         } => {
+            // `query run <name>` runs a saved query, `-f FILE` and `-` run a
+            // script; anything else runs `sql`
+            let sql = match (command, file) {
+                (Some(QueryCommands::Run { name }), _) => Some(commands::query::saved_sql(name)?),
+                (_, Some(path)) => Some(commands::query::read_script(path)?),
+                _ if sql.as_deref() == Some("-") => {
+                    Some(commands::query::read_script(Path::new("-"))?)
+                }
+                _ => sql.clone(),
+            };
+            ensure_data_tag(cli)?;
             let format = get_output_format(*json, *csv, *table, *plain);
-            commands::query::run(sql.as_deref(), false, format)
+            let options = QueryOptions {
+                timeout: timeout.map(Duration::from_secs),
+                max_rows: *max_rows,
+                memory_limit: memory_limit.clone(),
+                as_of: *as_of,
+                ..Default::default()
+            };
+            commands::query::run(
+                sql.as_deref(),
+                params,
+                format,
+                *precision,
+                options,
+                out.as_deref(),
+                *explain,
+            )
         }
 
         // Refresh command - fetch fresh data
         Commands::Refresh => {
-            commands::refresh::run(cli.quiet, cli.use_api, cli.profile.as_deref()).await
+            let source = data_source(cli)?;
+            commands::refresh::run(cli.quiet, cli.use_api, cli.profile.as_deref(), source).await
         }
 
         // Tables command - list available tables
-        Commands::Tables { table } => commands::tables::run(table.as_deref()),
+        Commands::Tables { table } => {
+            ensure_data_tag(cli)?;
+            commands::tables::run(table.as_deref())
+        }
 
         // Compare command - side-by-side model comparison
         Commands::Compare {
             models,
             verbose,
+            as_of,
             json,
             csv,
             table,
             plain,
         } => {
             let format = get_output_format(*json, *csv, *table, *plain);
-            let client = HostedDataClient::new()?;
-            let llm_models = client.get_llm_models(false).await?;
+            let client = HostedDataClient::with_source(data_source(cli)?)?;
+            let llm_models = match as_of {
+                Some(date) => client.get_llm_models_as_of(*date)?,
+                None => client.get_llm_models(false).await?,
+            };
             commands::compare::run(&llm_models, models, *verbose, format)
         }
 
@@ -62,14 +137,18 @@ async fn run() -> Result<()> {
             output,
             requests,
             period,
+            as_of,
             json,
             csv,
             table,
             plain,
         } => {
             let format = get_output_format(*json, *csv, *table, *plain);
-            let client = HostedDataClient::new()?;
-            let llm_models = client.get_llm_models(false).await?;
+            let client = HostedDataClient::with_source(data_source(cli)?)?;
+            let llm_models = match as_of {
+                Some(date) => client.get_llm_models_as_of(*date)?,
+                None => client.get_llm_models(false).await?,
+            };
             commands::cost::run(
                 &llm_models,
                 models,
@@ -81,8 +160,64 @@ async fn run() -> Result<()> {
             )
         }
 
+        // Diff command - compare data snapshots
+        Commands::Diff {
+            from,
+            to,
+            json,
+            csv,
+            table,
+            plain,
+        } => {
+            let format = get_output_format(*json, *csv, *table, *plain);
+            commands::diff::run(from.as_deref(), to.as_deref(), format)
+        }
+
+        // Trend command - values across data snapshots
+        Commands::Trend {
+            models,
+            field,
+            json,
+            csv,
+            table,
+            plain,
+        } => {
+            let format = get_output_format(*json, *csv, *table, *plain);
+            commands::trend::run(models, field, format)
+        }
+
+        // Watchlist
+        Commands::Watch { command } => match command {
+            WatchCommands::Add {
+                model,
+                max_price,
+                max_input_price,
+                max_output_price,
+                min_intelligence,
+                min_coding,
+                min_tps,
+                max_latency,
+            } => {
+                let rule = WatchRule {
+                    max_price: *max_price,
+                    max_input_price: *max_input_price,
+                    max_output_price: *max_output_price,
+                    min_intelligence: *min_intelligence,
+                    min_coding: *min_coding,
+                    min_tps: *min_tps,
+                    max_latency: *max_latency,
+                };
+                commands::watch::add(model, rule, data_source(cli)?).await
+            }
+            WatchCommands::List => commands::watch::list(),
+            WatchCommands::Remove { model } => commands::watch::remove(model),
+            WatchCommands::Check { json, webhook } => {
+                commands::watch::check(*json, webhook.as_deref(), data_source(cli)?).await
+            }
+        },
+
         // Info command
-        Commands::Info => commands::info::run(),
+        Commands::Info => commands::info::run(data_source(cli)?),
 
         // Cache management
         Commands::Cache { command } => match command {
@@ -92,9 +227,11 @@ async fn run() -> Result<()> {
 
         // Profile management
         Commands::Profile { command } => match command {
-            ProfileCommands::Create { name, api_key } => {
-                commands::profile::create(name, api_key.as_deref())
-            }
+            ProfileCommands::Create {
+                name,
+                api_key,
+                data_url,
+            } => commands::profile::create(name, api_key.as_deref(), data_url.as_deref()),
             ProfileCommands::List => commands::profile::list(),
             ProfileCommands::Default { name } => commands::profile::set_default(name),
             ProfileCommands::Delete { name } => commands::profile::delete(name),
@@ -108,9 +245,29 @@ async fn run() -> Result<()> {
                 global,
                 force,
                 dry_run,
-            } => commands::skill::install(tool, *global, *force, *dry_run).await,
+            } => commands::skill::install(tool, *global, *force, *dry_run, cli.offline).await,
             SkillCommands::Uninstall { tool, global } => commands::skill::uninstall(tool, *global),
             SkillCommands::List => commands::skill::list(),
         },
     }
 }
+
+/// Resolve the hosted data source from the config and the global flags.
+fn data_source(cli: &Cli) -> Result<DataSource> {
+    let config = Config::load()?;
+    Ok(DataSource {
+        offline: cli.offline,
+        ..DataSource::resolve(&config, cli.profile.as_deref(), cli.data_tag.as_deref())?
+    })
+}
+
+/// Check that `--data-tag`, if given, matches the cached data, for commands
+/// that only read the cache.
+fn ensure_data_tag(cli: &Cli) -> Result<()> {
+    if cli.data_tag.is_none() {
+        return Ok(());
+    }
+    HostedDataClient::with_source(data_source(cli)?)?
+        .remote()
+        .ensure_cached_release()
+}
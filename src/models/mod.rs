@@ -2,8 +2,10 @@
 
 pub mod llm;
 pub mod media;
+pub mod pricing;
 pub mod response;
 
 pub use llm::*;
 pub use media::*;
+pub use pricing::*;
 pub use response::*;
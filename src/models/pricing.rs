@@ -0,0 +1,7 @@
+//! Pricing units shared by the cost calculator and the SQL macros.
+
+/// Number of tokens a listed price applies to (prices are per 1M tokens).
+pub const TOKENS_PER_PRICE_UNIT: f64 = 1_000_000.0;
+
+/// Days used to project a daily cost to a month.
+pub const DAYS_PER_MONTH: f64 = 30.0;
@@ -0,0 +1,25 @@
+//! Offline mode.
+//!
+//! Enabled with `--offline` or `WHICH_LLM_OFFLINE`, and passed down to the
+//! commands that use the network, e.g. in [`DataSource`]. They check
+//! [`ensure_online`] before every request, and cached data is used even when
+//! it is past its TTL. `file://` data mirrors are local and keep working.
+//!
+//! [`DataSource`]: crate::remote::DataSource
+
+use crate::error::{AppError, Result};
+
+/// Environment variable enabling offline mode.
+pub const OFFLINE_ENV: &str = "WHICH_LLM_OFFLINE";
+
+/// Fail if `offline` is set; `action` says what needed the network,
+/// e.g. "download skills.zip".
+pub fn ensure_online(offline: bool, action: &str) -> Result<()> {
+    if offline {
+        return Err(AppError::Offline(format!(
+            "can't {} without network access. Drop --offline or unset {} to allow it.",
+            action, OFFLINE_ENV
+        )));
+    }
+    Ok(())
+}
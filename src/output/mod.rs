@@ -20,6 +20,31 @@ pub enum OutputFormat {
     Plain,
 }
 
+impl OutputFormat {
+    /// Parse an output format name (e.g. from a REPL `.format` command).
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "markdown" | "md" => Some(Self::Markdown),
+            "json" => Some(Self::Json),
+            "csv" => Some(Self::Csv),
+            "table" => Some(Self::Table),
+            "plain" => Some(Self::Plain),
+            _ => None,
+        }
+    }
+
+    /// Get the format name as a string.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Markdown => "markdown",
+            Self::Json => "json",
+            Self::Csv => "csv",
+            Self::Table => "table",
+            Self::Plain => "plain",
+        }
+    }
+}
+
 /// Trait for types that can be formatted for output.
 pub trait Formattable: Serialize + Tabled {
     /// Get the headers for table/markdown/csv output.
@@ -2,8 +2,10 @@
 //!
 //! Writes data to Parquet files using DuckDB.
 
+use crate::crosswalk::CrosswalkRow;
 use crate::error::{AppError, Result};
 use crate::models::MediaModel;
+use crate::schema::CROSSWALK;
 use crate::sources::artificial_analysis::models::AaLlmRow;
 use crate::sources::artificial_analysis::schema::BENCHMARKS;
 use crate::sources::models_dev::models::ModelsDevRow;
@@ -161,6 +163,43 @@ pub fn write_models_parquet(rows: &[ModelsDevRow], path: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Write crosswalk links between benchmarks and models.dev entries to Parquet.
+pub fn write_crosswalk_parquet(rows: &[CrosswalkRow], path: &Path) -> Result<()> {
+    let conn = Connection::open_in_memory()
+        .map_err(|e| AppError::Cache(format!("DuckDB error: {}", e)))?;
+
+    conn.execute(&CROSSWALK.create_table_sql(), [])
+        .map_err(|e| AppError::Cache(format!("DuckDB error: {}", e)))?;
+
+    {
+        let mut appender = conn
+            .appender(CROSSWALK.name)
+            .map_err(|e| AppError::Cache(format!("DuckDB appender error: {}", e)))?;
+
+        for row in rows {
+            appender
+                .append_row(params![
+                    row.aa_id,
+                    row.aa_slug,
+                    row.provider_id,
+                    row.model_id,
+                    row.confidence,
+                    row.method,
+                ])
+                .map_err(|e| AppError::Cache(format!("DuckDB append error: {}", e)))?;
+        }
+    }
+
+    let path_str = path.to_string_lossy();
+    conn.execute(
+        &format!("COPY {} TO '{}' (FORMAT PARQUET)", CROSSWALK.name, path_str),
+        [],
+    )
+    .map_err(|e| AppError::Cache(format!("DuckDB error: {}", e)))?;
+
+    Ok(())
+}
+
 /// Write media models to a Parquet file.
 pub fn write_media_parquet(models: &[MediaModel], path: &Path) -> Result<()> {
     let conn = Connection::open_in_memory()
@@ -0,0 +1,176 @@
+//! Download progress.
+//!
+//! On a terminal, `which-llm refresh` shows a progress bar per file. On a
+//! non-terminal, or with `--quiet`, it writes one JSON event per line to
+//! stderr instead, e.g. `{"event":"done","file":"models.parquet","bytes":52311}`.
+
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use serde_json::json;
+use std::io::IsTerminal;
+
+/// How download progress is reported.
+pub enum Progress {
+    /// Report nothing.
+    Hidden,
+    /// Progress bars on stderr.
+    Bars(MultiProgress),
+    /// JSON lines on stderr.
+    Events,
+}
+
+impl Progress {
+    /// Progress for a refresh: bars on a terminal, events otherwise.
+    pub fn for_refresh(quiet: bool) -> Self {
+        if !quiet && std::io::stderr().is_terminal() {
+            Self::Bars(MultiProgress::new())
+        } else {
+            Self::Events
+        }
+    }
+
+    /// Report a file that is already up to date.
+    pub fn unchanged(&self, file: &str) {
+        match self {
+            Self::Hidden => {}
+            Self::Bars(bars) => {
+                let _ = bars.println(format!("{} is up to date", file));
+            }
+            Self::Events => emit(json!({ "event": "unchanged", "file": file })),
+        }
+    }
+
+    /// Start reporting the download of a file of `total` bytes.
+    pub fn download(&self, file: &str, total: u64) -> FileProgress {
+        let bar = match self {
+            Self::Hidden => None,
+            Self::Bars(bars) => {
+                let style = ProgressStyle::with_template(
+                    "{prefix:>22} [{bar:30}] {bytes}/{total_bytes} {bytes_per_sec} {msg}",
+                )
+                .unwrap_or_else(|_| ProgressStyle::default_bar())
+                .progress_chars("=> ");
+                Some(
+                    bars.add(
+                        ProgressBar::new(total)
+                            .with_style(style)
+                            .with_prefix(file.to_string()),
+                    ),
+                )
+            }
+            Self::Events => {
+                emit(json!({ "event": "start", "file": file, "total": total }));
+                None
+            }
+        };
+
+        FileProgress {
+            file: file.to_string(),
+            total,
+            bytes: 0,
+            reported: 0,
+            events: matches!(self, Self::Events),
+            bar,
+        }
+    }
+}
+
+/// Progress of one file download.
+pub struct FileProgress {
+    file: String,
+    total: u64,
+    bytes: u64,
+    /// Last reported tenth of `total`, so events are throttled.
+    reported: u64,
+    events: bool,
+    bar: Option<ProgressBar>,
+}
+
+impl FileProgress {
+    /// Progress that reports nothing.
+    pub fn hidden(file: &str) -> Self {
+        Progress::Hidden.download(file, 0)
+    }
+
+    /// Record `n` more bytes received.
+    pub fn advance(&mut self, n: u64) {
+        self.bytes += n;
+        if let Some(bar) = &self.bar {
+            bar.inc(n);
+        }
+
+        let tenth = (self.bytes * 10)
+            .checked_div(self.total)
+            .unwrap_or(0)
+            .min(10);
+        if self.events && tenth > self.reported && tenth < 10 {
+            self.reported = tenth;
+            emit(json!({
+                "event": "progress",
+                "file": self.file,
+                "bytes": self.bytes,
+                "total": self.total,
+            }));
+        }
+    }
+
+    /// Start counting from zero again, when a download restarts.
+    pub fn restart(&mut self) {
+        self.bytes = 0;
+        self.reported = 0;
+        if let Some(bar) = &self.bar {
+            bar.set_position(0);
+        }
+    }
+
+    /// Finish a successful download.
+    pub fn done(&self) {
+        if let Some(bar) = &self.bar {
+            bar.finish();
+        }
+        if self.events {
+            emit(json!({ "event": "done", "file": self.file, "bytes": self.bytes }));
+        }
+    }
+
+    /// Finish a failed download.
+    pub fn fail(&self, error: &str) {
+        if let Some(bar) = &self.bar {
+            bar.abandon_with_message(error.to_string());
+        }
+        if self.events {
+            emit(json!({ "event": "failed", "file": self.file, "error": error }));
+        }
+    }
+
+    /// Bytes received so far.
+    pub fn bytes(&self) -> u64 {
+        self.bytes
+    }
+}
+
+/// Write a progress event to stderr.
+fn emit(event: serde_json::Value) {
+    eprintln!("{}", event);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_progress() {
+        let mut progress = FileProgress::hidden("benchmarks.parquet");
+        progress.advance(10);
+        progress.advance(5);
+        assert_eq!(progress.bytes(), 15);
+
+        // Events are throttled to tenths of the total
+        let mut progress = Progress::Events.download("models.parquet", 100);
+        progress.advance(5);
+        assert_eq!(progress.reported, 0);
+        progress.advance(30);
+        assert_eq!(progress.reported, 3);
+        progress.advance(65);
+        assert_eq!(progress.reported, 3);
+    }
+}
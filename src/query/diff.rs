@@ -0,0 +1,250 @@
+//! Differences between two data snapshots.
+//!
+//! Both snapshots are directories of cached Parquet files (the cache itself,
+//! a `history/<date>/` snapshot or any exported copy). The changes come back
+//! as one long-form result, so every output format works unchanged.
+
+use super::{batches_to_result, fetch_batches, sql_path, QueryResult};
+use crate::error::{AppError, Result};
+use crate::schema::{BENCHMARKS, MODELS, PRICE_FIELDS, SCORE_FIELDS};
+use duckdb::Connection;
+use std::path::Path;
+
+/// models.dev columns reported as capability changes.
+const CAPABILITY_FIELDS: &[&str] = &[
+    "tool_call",
+    "structured_output",
+    "reasoning",
+    "attachment",
+    "open_weights",
+    "context_window",
+    "max_output_tokens",
+    "input_modalities",
+    "output_modalities",
+];
+
+/// Kinds of change, in output order.
+const CHANGE_KINDS: &[&str] = &["added", "removed", "price", "score", "capability"];
+
+/// A table compared between the snapshots.
+struct DiffTable {
+    name: &'static str,
+    parquet_file: &'static str,
+    /// SQL expression identifying a row.
+    key: &'static str,
+    /// SQL expression naming a row in the output.
+    label: &'static str,
+    /// (change kind, columns) compared between matching rows.
+    fields: &'static [(&'static str, &'static [&'static str])],
+}
+
+const DIFF_TABLES: &[DiffTable] = &[
+    DiffTable {
+        name: "benchmarks",
+        parquet_file: BENCHMARKS.parquet_file,
+        key: "id",
+        label: "name",
+        fields: &[("price", PRICE_FIELDS), ("score", SCORE_FIELDS)],
+    },
+    DiffTable {
+        name: "models",
+        parquet_file: MODELS.parquet_file,
+        key: "provider_id || '/' || model_id",
+        label: "provider_id || '/' || model_id",
+        fields: &[("capability", CAPABILITY_FIELDS)],
+    },
+];
+
+/// Compare two snapshot directories.
+///
+/// Reports added and removed rows of `benchmarks` and `models`, then changed
+/// prices and scores of benchmarked models and changed capabilities of
+/// models.dev entries. Columns: `change`, `table`, `model`, `field`, `old`,
+/// `new` and `delta` (for numeric fields). Tables missing from either
+/// snapshot are skipped; `benchmarks` must be in both.
+pub fn diff_snapshots(old_dir: &Path, new_dir: &Path) -> Result<QueryResult> {
+    let conn = Connection::open_in_memory()
+        .map_err(|e| AppError::Query(format!("DuckDB error: {}", e)))?;
+
+    let mut selects = Vec::new();
+    for table in DIFF_TABLES {
+        let old_path = old_dir.join(table.parquet_file);
+        let new_path = new_dir.join(table.parquet_file);
+        match (old_path.exists(), new_path.exists()) {
+            (true, true) => {}
+            _ if table.name == BENCHMARKS.name => {
+                let missing = if old_path.exists() { new_dir } else { old_dir };
+                return Err(AppError::NotFound(format!(
+                    "{} in {}",
+                    table.parquet_file,
+                    missing.display()
+                )));
+            }
+            _ => continue,
+        }
+
+        conn.execute_batch(&format!(
+            "CREATE VIEW old_{name} AS SELECT {key} AS _key, {label} AS _label, * FROM read_parquet('{old}');
+             CREATE VIEW new_{name} AS SELECT {key} AS _key, {label} AS _label, * FROM read_parquet('{new}');",
+            name = table.name,
+            key = table.key,
+            label = table.label,
+            old = sql_path(&old_path),
+            new = sql_path(&new_path),
+        ))
+        .map_err(|e| AppError::Query(format!("Failed to read {}: {}", table.name, e)))?;
+
+        selects.extend(table_changes(table));
+    }
+
+    let order = CHANGE_KINDS
+        .iter()
+        .enumerate()
+        .map(|(i, kind)| format!("WHEN '{}' THEN {}", kind, i))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let sql = format!(
+        "SELECT * FROM ({}) ORDER BY CASE change {} END, \"table\", model, field",
+        selects.join("\nUNION ALL\n"),
+        order
+    );
+
+    fetch_batches(&conn, &sql, &[], None, None).map(batches_to_result)
+}
+
+/// Build the SELECTs reporting the changes to one table.
+fn table_changes(table: &DiffTable) -> Vec<String> {
+    let name = table.name;
+    let mut selects = vec![
+        format!(
+            "SELECT 'added' AS change, '{name}' AS \"table\", n._label AS model, \
+             NULL::VARCHAR AS field, NULL::VARCHAR AS old, NULL::VARCHAR AS new, NULL::DOUBLE AS delta \
+             FROM new_{name} n WHERE n._key NOT IN (SELECT _key FROM old_{name})"
+        ),
+        format!(
+            "SELECT 'removed', '{name}', o._label, NULL, NULL, NULL, NULL \
+             FROM old_{name} o WHERE o._key NOT IN (SELECT _key FROM new_{name})"
+        ),
+    ];
+
+    for (kind, fields) in table.fields {
+        for field in *fields {
+            selects.push(format!(
+                "SELECT '{kind}', '{name}', n._label, '{field}', o.{field}::VARCHAR, n.{field}::VARCHAR, \
+                 CASE WHEN typeof(n.{field}) NOT IN ('BOOLEAN', 'VARCHAR') \
+                 THEN TRY_CAST(n.{field} AS DOUBLE) - TRY_CAST(o.{field} AS DOUBLE) END \
+                 FROM new_{name} n JOIN old_{name} o ON n._key = o._key \
+                 WHERE o.{field} IS DISTINCT FROM n.{field}"
+            ));
+        }
+    }
+
+    selects
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::query::CellValue;
+    use tempfile::TempDir;
+
+    fn write(dir: &Path, file: &str, select: &str) {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(&format!(
+            "COPY ({}) TO '{}' (FORMAT PARQUET)",
+            select,
+            sql_path(&dir.join(file))
+        ))
+        .unwrap();
+    }
+
+    fn benchmarks(rows: &str) -> String {
+        let nulls = SCORE_FIELDS
+            .iter()
+            .chain(PRICE_FIELDS)
+            .filter(|f| !["intelligence", "input_price"].contains(*f))
+            .map(|f| format!("NULL::DOUBLE AS {}", f))
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!(
+            "SELECT *, {} FROM (VALUES {}) t(id, name, intelligence, input_price)",
+            nulls, rows
+        )
+    }
+
+    #[test]
+    fn test_diff_snapshots() {
+        let old = TempDir::new().unwrap();
+        let new = TempDir::new().unwrap();
+        write(
+            old.path(),
+            "benchmarks.parquet",
+            &benchmarks("('1', 'GPT-4o', 50.0, 2.5), ('2', 'Old Model', 30.0, 1.0)"),
+        );
+        write(
+            new.path(),
+            "benchmarks.parquet",
+            &benchmarks("('1', 'GPT-4o', 52.0, 2.0), ('3', 'New Model', 60.0, 3.0)"),
+        );
+
+        let result = diff_snapshots(old.path(), new.path()).unwrap();
+        let changes: Vec<(String, String, String)> = result
+            .rows
+            .iter()
+            .map(|row| {
+                let text = |v: &CellValue| match v {
+                    CellValue::Text(s) => s.clone(),
+                    _ => String::new(),
+                };
+                (text(&row[0]), text(&row[2]), text(&row[3]))
+            })
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                ("added".into(), "New Model".into(), "".into()),
+                ("removed".into(), "Old Model".into(), "".into()),
+                ("price".into(), "GPT-4o".into(), "input_price".into()),
+                ("score".into(), "GPT-4o".into(), "intelligence".into()),
+            ]
+        );
+        assert_eq!(result.rows[2][6], CellValue::Float(-0.5));
+    }
+
+    #[test]
+    fn test_diff_capabilities() {
+        let old = TempDir::new().unwrap();
+        let new = TempDir::new().unwrap();
+        for dir in [old.path(), new.path()] {
+            write(
+                dir,
+                "benchmarks.parquet",
+                &benchmarks("('1', 'GPT-4o', 50.0, 2.5)"),
+            );
+        }
+        let models = |tool_call: bool| {
+            let columns = CAPABILITY_FIELDS
+                .iter()
+                .filter(|f| **f != "tool_call")
+                .map(|f| format!("NULL AS {}", f))
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!(
+                "SELECT 'openai' AS provider_id, 'gpt-4o' AS model_id, {} AS tool_call, {}",
+                tool_call, columns
+            )
+        };
+        write(old.path(), "models.parquet", &models(false));
+        write(new.path(), "models.parquet", &models(true));
+
+        let result = diff_snapshots(old.path(), new.path()).unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result.rows[0][0], CellValue::from("capability"));
+        assert_eq!(result.rows[0][2], CellValue::from("openai/gpt-4o"));
+        assert_eq!(result.rows[0][5], CellValue::from("true"));
+        assert_eq!(result.rows[0][6], CellValue::Null);
+
+        let empty = TempDir::new().unwrap();
+        assert!(diff_snapshots(old.path(), empty.path()).is_err());
+    }
+}
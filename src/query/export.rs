@@ -0,0 +1,191 @@
+//! Export query results to files.
+//!
+//! Results are written straight from DuckDB's Arrow record batches, so types
+//! (and Parquet/Arrow IPC schemas) are preserved exactly.
+
+use crate::error::{AppError, Result};
+use arrow::datatypes::SchemaRef;
+use arrow::record_batch::RecordBatch;
+use parquet::arrow::ArrowWriter;
+use parquet::basic::Compression;
+use parquet::file::properties::WriterProperties;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+
+/// File format for `query --out`, inferred from the file extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Parquet,
+    Arrow,
+    Ndjson,
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    /// Infer the export format from a file extension.
+    pub fn from_path(path: &Path) -> Result<Self> {
+        let ext = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_lowercase())
+            .unwrap_or_default();
+
+        match ext.as_str() {
+            "parquet" => Ok(Self::Parquet),
+            "arrow" | "ipc" | "feather" => Ok(Self::Arrow),
+            "ndjson" | "jsonl" => Ok(Self::Ndjson),
+            "csv" => Ok(Self::Csv),
+            "json" => Ok(Self::Json),
+            _ => Err(AppError::Query(format!(
+                "Cannot infer export format from '{}'. Use a .parquet, .arrow, .ndjson, .csv or .json file.",
+                path.display()
+            ))),
+        }
+    }
+}
+
+/// Write record batches to a file, returning the number of rows written.
+pub fn write_batches(path: &Path, schema: SchemaRef, batches: &[RecordBatch]) -> Result<usize> {
+    let format = ExportFormat::from_path(path)?;
+    let file = BufWriter::new(File::create(path)?);
+    let export_err = |e: &dyn std::fmt::Display| {
+        AppError::Query(format!("Failed to write '{}': {}", path.display(), e))
+    };
+
+    match format {
+        ExportFormat::Parquet => {
+            let props = WriterProperties::builder()
+                .set_compression(Compression::SNAPPY)
+                .build();
+            let mut writer =
+                ArrowWriter::try_new(file, schema, Some(props)).map_err(|e| export_err(&e))?;
+            for batch in batches {
+                writer.write(batch).map_err(|e| export_err(&e))?;
+            }
+            writer.close().map_err(|e| export_err(&e))?;
+        }
+        ExportFormat::Arrow => {
+            let mut writer = arrow::ipc::writer::FileWriter::try_new(file, &schema)
+                .map_err(|e| export_err(&e))?;
+            for batch in batches {
+                writer.write(batch).map_err(|e| export_err(&e))?;
+            }
+            writer.finish().map_err(|e| export_err(&e))?;
+        }
+        ExportFormat::Ndjson => {
+            let mut writer = arrow::json::LineDelimitedWriter::new(file);
+            for batch in batches {
+                writer.write(batch).map_err(|e| export_err(&e))?;
+            }
+            writer.finish().map_err(|e| export_err(&e))?;
+        }
+        ExportFormat::Json => {
+            let mut writer = arrow::json::ArrayWriter::new(file);
+            for batch in batches {
+                writer.write(batch).map_err(|e| export_err(&e))?;
+            }
+            writer.finish().map_err(|e| export_err(&e))?;
+        }
+        ExportFormat::Csv => {
+            // Write the header even when there are no rows
+            let mut writer = arrow::csv::WriterBuilder::new()
+                .with_header(true)
+                .build(file);
+            let empty = RecordBatch::new_empty(schema);
+            let batches: Vec<&RecordBatch> = if batches.is_empty() {
+                vec![&empty]
+            } else {
+                batches.iter().collect()
+            };
+            for batch in batches {
+                writer.write(batch).map_err(|e| export_err(&e))?;
+            }
+        }
+    }
+
+    Ok(batches.iter().map(|b| b.num_rows()).sum())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{Float64Array, StringArray};
+    use arrow::datatypes::{DataType, Field, Schema};
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    fn sample_batch() -> RecordBatch {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("slug", DataType::Utf8, false),
+            Field::new("price", DataType::Float64, true),
+        ]));
+        RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(StringArray::from(vec!["gpt-4o", "claude"])),
+                Arc::new(Float64Array::from(vec![Some(0.075), None])),
+            ],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_export_format_from_path() {
+        assert_eq!(
+            ExportFormat::from_path(Path::new("out.parquet")).unwrap(),
+            ExportFormat::Parquet
+        );
+        assert_eq!(
+            ExportFormat::from_path(Path::new("out.ARROW")).unwrap(),
+            ExportFormat::Arrow
+        );
+        assert_eq!(
+            ExportFormat::from_path(Path::new("out.jsonl")).unwrap(),
+            ExportFormat::Ndjson
+        );
+        assert!(ExportFormat::from_path(Path::new("out.xlsx")).is_err());
+        assert!(ExportFormat::from_path(Path::new("out")).is_err());
+    }
+
+    #[test]
+    fn test_write_batches_text_formats() {
+        let temp_dir = TempDir::new().unwrap();
+        let batch = sample_batch();
+
+        let path = temp_dir.path().join("out.ndjson");
+        let rows = write_batches(&path, batch.schema(), &[batch.clone()]).unwrap();
+        assert_eq!(rows, 2);
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(content.contains(r#"{"slug":"gpt-4o","price":0.075}"#));
+
+        let path = temp_dir.path().join("out.csv");
+        write_batches(&path, batch.schema(), &[batch]).unwrap();
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(content.starts_with("slug,price\n"));
+        assert!(content.contains("gpt-4o,0.075"));
+    }
+
+    #[test]
+    fn test_write_batches_parquet_roundtrip() {
+        let temp_dir = TempDir::new().unwrap();
+        let batch = sample_batch();
+        let path = temp_dir.path().join("out.parquet");
+
+        write_batches(&path, batch.schema(), &[batch]).unwrap();
+
+        let conn = duckdb::Connection::open_in_memory().unwrap();
+        let price: f64 = conn
+            .query_row(
+                &format!(
+                    "SELECT price FROM read_parquet('{}') WHERE slug = 'gpt-4o'",
+                    path.to_string_lossy().replace('\\', "/")
+                ),
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(price, 0.075);
+    }
+}
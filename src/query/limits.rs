@@ -0,0 +1,120 @@
+//! Resource limits for query execution.
+//!
+//! `--max-rows` limits queries to one row more than requested and
+//! `--timeout` interrupts the DuckDB connection from a watchdog thread. Rows
+//! fetched before the interrupt are kept and marked as truncated.
+
+use duckdb::Connection;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+/// Why a query result is incomplete.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Truncation {
+    /// More rows were available than `--max-rows` allows.
+    MaxRows(usize),
+    /// The query was interrupted by `--timeout` while rows were fetched.
+    Timeout(Duration),
+}
+
+impl fmt::Display for Truncation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MaxRows(max) => write!(
+                f,
+                "Output truncated to the first {} rows (--max-rows).",
+                max
+            ),
+            Self::Timeout(timeout) => write!(
+                f,
+                "Query interrupted after {}s (--timeout); results are incomplete.",
+                timeout.as_secs()
+            ),
+        }
+    }
+}
+
+/// Interrupts the running query on a connection once the timeout elapses.
+pub struct Watchdog {
+    timeout: Duration,
+    /// Set before the interrupt, so a result cut short can be told apart.
+    fired: Arc<AtomicBool>,
+    done: mpsc::Sender<()>,
+    handle: JoinHandle<bool>,
+}
+
+impl Watchdog {
+    /// Start watching the connection.
+    pub fn start(conn: &Connection, timeout: Duration) -> Self {
+        let interrupt = conn.interrupt_handle();
+        let (done, rx) = mpsc::channel::<()>();
+        let fired = Arc::new(AtomicBool::new(false));
+
+        let flag = Arc::clone(&fired);
+        let handle = thread::spawn(move || match rx.recv_timeout(timeout) {
+            Err(RecvTimeoutError::Timeout) => {
+                flag.store(true, Ordering::SeqCst);
+                interrupt.interrupt();
+                true
+            }
+            _ => false,
+        });
+
+        Self {
+            timeout,
+            fired,
+            done,
+            handle,
+        }
+    }
+
+    /// Get the truncation to report if the query has been interrupted.
+    pub fn truncation(&self) -> Option<Truncation> {
+        self.fired
+            .load(Ordering::SeqCst)
+            .then_some(Truncation::Timeout(self.timeout))
+    }
+
+    /// Stop watching, returning whether the query was interrupted.
+    pub fn stop(self) -> bool {
+        let _ = self.done.send(());
+        self.handle.join().unwrap_or(false)
+    }
+}
+
+/// Build the `SET memory_limit` statement for a user-supplied limit.
+pub fn memory_limit_sql(limit: &str) -> String {
+    format!("SET memory_limit = '{}';", limit.replace('\'', "''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truncation_display() {
+        assert!(Truncation::MaxRows(100)
+            .to_string()
+            .contains("first 100 rows"));
+        assert!(Truncation::Timeout(Duration::from_secs(5))
+            .to_string()
+            .contains("after 5s"));
+    }
+
+    #[test]
+    fn test_watchdog_stops_without_interrupting() {
+        let conn = Connection::open_in_memory().unwrap();
+        let watchdog = Watchdog::start(&conn, Duration::from_secs(60));
+        assert_eq!(watchdog.truncation(), None);
+        assert!(!watchdog.stop());
+    }
+
+    #[test]
+    fn test_memory_limit_sql() {
+        assert_eq!(memory_limit_sql("512MB"), "SET memory_limit = '512MB';");
+    }
+}
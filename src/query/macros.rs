@@ -0,0 +1,143 @@
+//! Built-in SQL macros for cost and value calculations.
+//!
+//! Registered on every query connection so that queries reuse the same math
+//! as `which-llm cost` instead of re-deriving it by hand. A macro can't read
+//! the calling row, so prices and scores are passed in as arguments:
+//! `request_cost(10000, 2000, input_price, output_price)`.
+
+use crate::models::{DAYS_PER_MONTH, TOKENS_PER_PRICE_UNIT};
+
+/// A built-in SQL macro.
+#[derive(Debug, Clone)]
+pub struct SqlMacro {
+    /// Call signature shown in `which-llm tables`.
+    pub signature: &'static str,
+    /// One-line description.
+    pub description: &'static str,
+    /// Macro parameters (DuckDB syntax, including defaults).
+    params: &'static str,
+    /// Macro body; `{tokens}` and `{days}` are replaced with the cost constants.
+    body: &'static str,
+}
+
+impl SqlMacro {
+    /// Get the macro name.
+    pub fn name(&self) -> &'static str {
+        self.signature
+            .split_once('(')
+            .map(|(name, _)| name)
+            .unwrap_or(self.signature)
+    }
+
+    /// Generate the CREATE MACRO statement.
+    pub fn create_sql(&self) -> String {
+        let body = self
+            .body
+            .replace("{tokens}", &format!("{:.1}", TOKENS_PER_PRICE_UNIT))
+            .replace("{days}", &format!("{:.1}", DAYS_PER_MONTH));
+        format!(
+            "CREATE OR REPLACE MACRO {}({}) AS {};",
+            self.name(),
+            self.params,
+            body
+        )
+    }
+}
+
+/// All built-in macros.
+pub const MACROS: &[SqlMacro] = &[
+    SqlMacro {
+        signature: "blended_price(input_price, output_price, ratio := 3)",
+        description: "Price per 1M tokens at an input:output ratio (3 = AA's 3:1 blend)",
+        params: "input_price, output_price, ratio := 3",
+        body: "(input_price * ratio + output_price) / (ratio + 1)",
+    },
+    SqlMacro {
+        signature: "request_cost(input_tokens, output_tokens, input_price, output_price)",
+        description: "USD cost of one request (same as `which-llm cost`)",
+        params: "input_tokens, output_tokens, input_price, output_price",
+        body: "CASE WHEN input_price IS NULL AND output_price IS NULL THEN NULL \
+               ELSE COALESCE(input_tokens / {tokens} * input_price, 0) \
+               + COALESCE(output_tokens / {tokens} * output_price, 0) END",
+    },
+    SqlMacro {
+        signature:
+            "monthly_cost(input_tokens, output_tokens, requests_per_day, input_price, output_price)",
+        description: "USD cost per month (30 days) at a daily request volume",
+        params: "input_tokens, output_tokens, requests_per_day, input_price, output_price",
+        body: "request_cost(input_tokens, output_tokens, input_price, output_price) \
+               * requests_per_day * {days}",
+    },
+    SqlMacro {
+        signature: "intelligence_per_dollar(intelligence, price)",
+        description: "Intelligence index points per USD of blended price (NULL if free/unknown)",
+        params: "intelligence, price",
+        body: "CASE WHEN price > 0 THEN intelligence / price END",
+    },
+    SqlMacro {
+        signature:
+            "weighted_score(intelligence, price, tps, quality_weight, cost_weight, speed_weight)",
+        description: "Weighted score: intelligence*wq + 100/price*wc + tps/10*ws",
+        params: "intelligence, price, tps, quality_weight, cost_weight, speed_weight",
+        body: "intelligence * quality_weight \
+               + CASE WHEN price > 0 THEN 100 / price ELSE 0 END * cost_weight \
+               + COALESCE(tps, 0) / 10 * speed_weight",
+    },
+];
+
+/// Generate the statements registering every built-in macro.
+pub fn create_macros_sql() -> String {
+    MACROS
+        .iter()
+        .map(SqlMacro::create_sql)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use duckdb::Connection;
+
+    /// Evaluate a SQL expression with the macros registered.
+    fn eval(expr: &str) -> Option<f64> {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(&create_macros_sql()).unwrap();
+        conn.query_row(&format!("SELECT ({})::DOUBLE", expr), [], |row| row.get(0))
+            .unwrap()
+    }
+
+    fn assert_close(actual: Option<f64>, expected: f64) {
+        let actual = actual.expect("expected a value");
+        assert!((actual - expected).abs() < 1e-9, "{actual} != {expected}");
+    }
+
+    #[test]
+    fn test_macro_name() {
+        assert_eq!(MACROS[0].name(), "blended_price");
+    }
+
+    #[test]
+    fn test_blended_price() {
+        assert_close(eval("blended_price(1.0, 5.0)"), 2.0);
+        assert_close(eval("blended_price(1.0, 5.0, 1)"), 3.0);
+    }
+
+    #[test]
+    fn test_request_cost() {
+        assert_close(eval("request_cost(1000000, 500000, 2.0, 8.0)"), 6.0);
+        assert_close(eval("request_cost(1000000, 500000, 2.0, NULL)"), 2.0);
+        assert_eq!(eval("request_cost(1000000, 500000, NULL, NULL)"), None);
+        assert_close(eval("monthly_cost(1000000, 0, 10, 1.0, NULL)"), 300.0);
+    }
+
+    #[test]
+    fn test_value_macros() {
+        assert_close(eval("intelligence_per_dollar(50.0, 2.0)"), 25.0);
+        assert_eq!(eval("intelligence_per_dollar(50.0, 0.0)"), None);
+        assert_close(
+            eval("weighted_score(50.0, 10.0, 100.0, 0.4, 0.4, 0.2)"),
+            50.0 * 0.4 + 10.0 * 0.4 + 10.0 * 0.2,
+        );
+    }
+}
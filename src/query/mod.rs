@@ -0,0 +1,1613 @@
+//! SQL query execution via DuckDB.
+//!
+//! Registers each cached Parquet file as a DuckDB view so that queries can use
+//! any DuckDB SQL (QUALIFY, PIVOT, FROM-first, SUMMARIZE, ...) against the
+//! plain table names.
+
+pub mod diff;
+pub mod export;
+mod limits;
+pub mod macros;
+pub mod params;
+pub mod sandbox;
+pub mod script;
+mod suggest;
+mod value;
+
+pub use limits::Truncation;
+pub use value::{CellValue, DEFAULT_PRECISION};
+
+use crate::error::{AppError, Result};
+use crate::history::{self, Snapshot, HISTORY_SUFFIX};
+use crate::output::OutputFormat;
+use crate::schema::{Column, TableDef, ViewDef, ALL_TABLES, ALL_VIEWS};
+use chrono::NaiveDate;
+use comfy_table::{presets::ASCII_BORDERS_ONLY_CONDENSED, Table};
+use duckdb::arrow::datatypes::SchemaRef;
+use duckdb::arrow::record_batch::RecordBatch;
+use duckdb::types::Value;
+use duckdb::Connection;
+use serde::ser::{Serialize, SerializeMap, Serializer};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Schema information for a table (used for --tables display).
+pub struct TableSchema {
+    pub name: &'static str,
+    pub columns: &'static [Column],
+}
+
+/// Result of a SQL query.
+pub struct QueryResult {
+    /// Column names.
+    pub columns: Vec<String>,
+    /// Rows of typed values.
+    pub rows: Vec<Vec<CellValue>>,
+    /// Set when the rows are incomplete (row cap, or a timeout while rows
+    /// were fetched).
+    pub truncated: Option<Truncation>,
+}
+
+impl QueryResult {
+    /// Check if the result is empty.
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Get the number of rows.
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+}
+
+/// Raw Arrow output of a SQL query (used for file exports).
+pub struct QueryBatches {
+    /// Result schema (available even when there are no rows).
+    pub schema: SchemaRef,
+    /// Record batches as returned by DuckDB.
+    pub batches: Vec<RecordBatch>,
+    /// Set when the batches are incomplete (row cap, or a timeout while rows
+    /// were fetched).
+    pub truncated: Option<Truncation>,
+}
+
+/// Options controlling query execution.
+#[derive(Debug, Clone)]
+pub struct QueryOptions {
+    /// Only allow read-only statements and lock down file/extension access.
+    pub sandbox: bool,
+    /// Interrupt queries running longer than this.
+    pub timeout: Option<Duration>,
+    /// Return at most this many rows.
+    pub max_rows: Option<usize>,
+    /// DuckDB memory limit (e.g. "512MB").
+    pub memory_limit: Option<String>,
+    /// User-defined views from config (name -> SELECT).
+    pub views: BTreeMap<String, String>,
+    /// Query the snapshot in effect on this date instead of the current cache.
+    pub as_of: Option<NaiveDate>,
+}
+
+impl Default for QueryOptions {
+    fn default() -> Self {
+        Self {
+            sandbox: true,
+            timeout: None,
+            max_rows: None,
+            memory_limit: None,
+            views: BTreeMap::new(),
+            as_of: None,
+        }
+    }
+}
+
+/// SQL query executor.
+///
+/// Holds a single DuckDB connection for the lifetime of the executor, with
+/// every cached table registered as a view over its Parquet file, followed by
+/// the user-defined views from config.
+pub struct QueryExecutor {
+    /// Directory the tables are read from: the cache, or a snapshot with `as_of`.
+    cache_dir: PathBuf,
+    conn: Connection,
+    options: QueryOptions,
+    /// Snapshots backing the `<table>_history` views (up to `as_of`).
+    snapshots: Vec<Snapshot>,
+    /// User views that could not be registered, with the reason.
+    view_errors: BTreeMap<String, String>,
+}
+
+impl QueryExecutor {
+    /// Create a new query executor with default options.
+    pub fn new(cache_dir: PathBuf) -> Result<Self> {
+        Self::with_options(cache_dir, QueryOptions::default())
+    }
+
+    /// Create a new query executor and register the cached tables as views.
+    pub fn with_options(cache_dir: PathBuf, options: QueryOptions) -> Result<Self> {
+        let conn = Connection::open_in_memory()
+            .map_err(|e| AppError::Query(format!("DuckDB error: {}", e)))?;
+
+        let snapshots: Vec<Snapshot> = history::list_snapshots(&cache_dir)?
+            .into_iter()
+            .filter(|s| !matches!(options.as_of, Some(as_of) if s.date > as_of))
+            .collect();
+        let cache_dir = match options.as_of {
+            Some(as_of) => history::snapshot_as_of(&cache_dir, as_of)?.dir,
+            None => cache_dir,
+        };
+
+        let mut executor = Self {
+            cache_dir,
+            conn,
+            options,
+            snapshots,
+            view_errors: BTreeMap::new(),
+        };
+        executor.register_views()?;
+        executor
+            .conn
+            .execute_batch(&macros::create_macros_sql())
+            .map_err(|e| AppError::Query(format!("Failed to register macros: {}", e)))?;
+        executor.view_errors = executor.register_history_views();
+        let builtin_view_errors = executor.register_builtin_views();
+        executor.view_errors.extend(builtin_view_errors);
+
+        if let Some(limit) = &executor.options.memory_limit {
+            executor
+                .conn
+                .execute_batch(&limits::memory_limit_sql(limit))
+                .map_err(|e| AppError::Query(format!("Invalid memory limit '{}': {}", limit, e)))?;
+        }
+
+        if executor.options.sandbox {
+            executor
+                .conn
+                .execute_batch(&sandbox::lockdown_sql(&executor.cached_paths()))
+                .map_err(|e| AppError::Query(format!("Failed to enable sandbox: {}", e)))?;
+        }
+
+        // User views come from config files found on disk, so they are only
+        // created once the connection is locked down
+        let user_view_errors = executor.register_user_views();
+        executor.view_errors.extend(user_view_errors);
+
+        Ok(executor)
+    }
+
+    /// Get the Parquet paths of all cached tables and their snapshots.
+    fn cached_paths(&self) -> Vec<PathBuf> {
+        std::iter::once(&self.cache_dir)
+            .chain(self.snapshots.iter().map(|s| &s.dir))
+            .flat_map(|dir| ALL_TABLES.iter().map(move |t| dir.join(t.parquet_file)))
+            .filter(|p| p.exists())
+            .collect()
+    }
+
+    /// Get the snapshot files of a table, oldest first.
+    fn snapshot_files(&self, table_def: &TableDef) -> Vec<(NaiveDate, PathBuf)> {
+        self.snapshots
+            .iter()
+            .map(|s| (s.date, s.dir.join(table_def.parquet_file)))
+            .filter(|(_, path)| path.exists())
+            .collect()
+    }
+
+    /// Register a view for every table whose Parquet file is cached.
+    ///
+    /// Tables that are not cached are skipped; referencing them produces a
+    /// "run refresh first" error at query time.
+    fn register_views(&self) -> Result<()> {
+        for table_def in ALL_TABLES {
+            let parquet_path = self.cache_dir.join(table_def.parquet_file);
+            if !parquet_path.exists() {
+                continue;
+            }
+
+            self.conn
+                .execute_batch(&create_view_sql(table_def.name, &parquet_path))
+                .map_err(|e| {
+                    AppError::Query(format!(
+                        "Failed to register table '{}': {}. Run '{}' to re-download it.",
+                        table_def.name, e, table_def.command
+                    ))
+                })?;
+        }
+        Ok(())
+    }
+
+    /// Register a `<table>_history` view stacking every snapshot of each
+    /// table, with the snapshot date in a leading `snapshot_date` column.
+    ///
+    /// Returns the views that failed (e.g. over a corrupt snapshot file).
+    fn register_history_views(&self) -> BTreeMap<String, String> {
+        let mut errors = BTreeMap::new();
+
+        for table_def in ALL_TABLES {
+            let selects: Vec<String> = self
+                .snapshot_files(table_def)
+                .iter()
+                .map(|(date, path)| {
+                    format!(
+                        "SELECT DATE '{}' AS snapshot_date, * FROM read_parquet('{}')",
+                        date,
+                        sql_path(path)
+                    )
+                })
+                .collect();
+            if selects.is_empty() {
+                continue;
+            }
+
+            let name = format!("{}{}", table_def.name, HISTORY_SUFFIX);
+            let create = format!(
+                "CREATE OR REPLACE VIEW {} AS {}",
+                name,
+                selects.join(" UNION ALL BY NAME ")
+            );
+            if let Err(e) = self.conn.execute_batch(&create) {
+                errors.insert(name, e.to_string());
+            }
+        }
+
+        errors
+    }
+
+    /// Whether every table a built-in view reads is cached.
+    fn view_available(&self, view: &ViewDef) -> bool {
+        view.requires
+            .iter()
+            .all(|t| self.cache_dir.join(t.parquet_file).exists())
+    }
+
+    /// Register the built-in views whose tables are cached, returning the
+    /// ones that failed (e.g. over a cache written by an older version).
+    fn register_builtin_views(&self) -> BTreeMap<String, String> {
+        ALL_VIEWS
+            .iter()
+            .filter(|view| self.view_available(view))
+            .filter_map(|view| {
+                let create = format!("CREATE OR REPLACE VIEW {} AS {}", view.name, view.sql);
+                self.conn
+                    .execute_batch(&create)
+                    .err()
+                    .map(|e| (view.name.to_string(), e.to_string()))
+            })
+            .collect()
+    }
+
+    /// Register the user-defined views, returning the ones that failed.
+    ///
+    /// Views may build on each other, so registration repeats until no more
+    /// views succeed. A broken view (e.g. over an uncached table) does not
+    /// stop the others; querying it reports why it is unavailable.
+    fn register_user_views(&self) -> BTreeMap<String, String> {
+        let mut pending: BTreeMap<String, String> = BTreeMap::new();
+        let mut errors = BTreeMap::new();
+
+        for (name, sql) in &self.options.views {
+            let builtin = ALL_TABLES.iter().any(|t| {
+                t.name == name.as_str() || name.strip_suffix(HISTORY_SUFFIX) == Some(t.name)
+            }) || ALL_VIEWS.iter().any(|v| v.name == name.as_str());
+            let invalid = if builtin {
+                Some("it has the same name as a built-in table".to_string())
+            } else if !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+                Some("view names may only contain letters, digits and '_'".to_string())
+            } else if script::split_statements(sql).len() > 1 {
+                Some("a view must be a single SELECT statement".to_string())
+            } else if self.options.sandbox {
+                sandbox::check_statement(sql).err().map(|e| e.to_string())
+            } else {
+                None
+            };
+
+            match invalid {
+                Some(reason) => {
+                    errors.insert(name.clone(), reason);
+                }
+                None => {
+                    pending.insert(name.clone(), sql.clone());
+                }
+            }
+        }
+
+        loop {
+            let before = pending.len();
+            pending.retain(|name, sql| {
+                let create = format!(
+                    "CREATE OR REPLACE VIEW {} AS {}",
+                    name,
+                    sql.trim().trim_end_matches(';')
+                );
+                // Prepared, so only a single statement can ever run
+                match self
+                    .conn
+                    .prepare(&create)
+                    .and_then(|mut stmt| stmt.execute([]))
+                {
+                    Ok(_) => {
+                        errors.remove(name);
+                        false
+                    }
+                    Err(e) => {
+                        errors.insert(name.clone(), e.to_string());
+                        true
+                    }
+                }
+            });
+            if pending.is_empty() || pending.len() == before {
+                break;
+            }
+        }
+
+        errors
+    }
+
+    /// Execute a SQL query and return the raw Arrow record batches.
+    pub fn execute_batches(&self, sql: &str) -> Result<QueryBatches> {
+        self.run_statement(sql, &[])
+    }
+
+    /// Execute a query with `:name` placeholders bound to `params` and
+    /// return the raw Arrow record batches.
+    pub fn execute_batches_with_params(
+        &self,
+        sql: &str,
+        params: &[(String, String)],
+    ) -> Result<QueryBatches> {
+        let bound = params::bind(sql, params)?;
+        self.run_statement(&bound.sql, &bound.values)
+    }
+
+    /// Execute a SQL query and return the results.
+    pub fn execute(&self, sql: &str) -> Result<QueryResult> {
+        Ok(batches_to_result(self.execute_batches(sql)?))
+    }
+
+    /// Execute a query with `:name` placeholders bound to `params` and
+    /// return the results.
+    pub fn execute_with_params(
+        &self,
+        sql: &str,
+        params: &[(String, String)],
+    ) -> Result<QueryResult> {
+        Ok(batches_to_result(
+            self.execute_batches_with_params(sql, params)?,
+        ))
+    }
+
+    /// Run a single statement with positional parameter values.
+    fn run_statement(&self, sql: &str, values: &[Value]) -> Result<QueryBatches> {
+        if self.options.sandbox {
+            sandbox::check_statement(sql)?;
+        }
+
+        execute_duckdb_query(&self.conn, sql, values, &self.options)
+            .map_err(|e| self.missing_table_error(&e).unwrap_or(e))
+    }
+
+    /// Describe how a query would run without executing it.
+    ///
+    /// Shows the SQL sent to DuckDB (with `:name` placeholders bound to
+    /// `params`), the definitions of the views it reads and DuckDB's
+    /// `EXPLAIN` plan.
+    pub fn explain(&self, sql: &str, params: &[(String, String)]) -> Result<String> {
+        use std::fmt::Write;
+
+        if self.options.sandbox {
+            sandbox::check_statement(sql)?;
+        }
+
+        let bound = params::bind(sql, params)?;
+        let plan = fetch_batches(
+            &self.conn,
+            &format!("EXPLAIN {}", bound.sql),
+            &bound.values,
+            None,
+            None,
+        )
+        .map_err(|e| self.missing_table_error(&e).unwrap_or(e))?;
+
+        let mut output = String::new();
+        writeln!(
+            output,
+            "-- SQL sent to DuckDB (verbatim; tables are views over the cached Parquet files)"
+        )
+        .unwrap();
+        writeln!(output, "{}\n", bound.sql.trim()).unwrap();
+
+        if !bound.values.is_empty() {
+            writeln!(output, "-- Parameters").unwrap();
+            for (i, value) in bound.values.iter().enumerate() {
+                writeln!(output, "${} = {:?}", i + 1, value).unwrap();
+            }
+            writeln!(output).unwrap();
+        }
+
+        writeln!(output, "-- Views").unwrap();
+        let identifiers = sql_identifiers(sql);
+        for view in ALL_VIEWS
+            .iter()
+            .filter(|v| identifiers.iter().any(|i| i == v.name))
+        {
+            writeln!(output, "CREATE VIEW {} AS {};", view.name, view.sql).unwrap();
+        }
+        for table_def in ALL_TABLES
+            .iter()
+            .filter(|t| identifiers.iter().any(|i| i == t.name))
+        {
+            let parquet_path = self.cache_dir.join(table_def.parquet_file);
+            if parquet_path.exists() {
+                writeln!(
+                    output,
+                    "{};",
+                    create_view_sql(table_def.name, &parquet_path)
+                )
+                .unwrap();
+            }
+        }
+
+        writeln!(output, "\n-- Plan").unwrap();
+        // EXPLAIN returns (explain_key, explain_value) rows; the value holds the plan
+        for batch in &plan.batches {
+            if batch.num_columns() < 2 {
+                continue;
+            }
+            for row in 0..batch.num_rows() {
+                let value = CellValue::from_arrow(batch.column(1).as_ref(), row);
+                writeln!(output, "{}", value.to_text()).unwrap();
+            }
+        }
+
+        Ok(output)
+    }
+
+    /// Turn DuckDB's "table does not exist" error for a known but uncached
+    /// table or built-in view into a hint to fetch the data, or for a view
+    /// that failed to register into the reason it failed.
+    fn missing_table_error(&self, error: &AppError) -> Option<AppError> {
+        let msg = error.to_string().to_lowercase();
+        let missing =
+            |name: &str| msg.contains(&format!("table with name {} does not exist", name));
+
+        if let Some(t) = ALL_TABLES
+            .iter()
+            .filter(|t| !self.cache_dir.join(t.parquet_file).exists())
+            .find(|t| missing(t.name))
+        {
+            return Some(AppError::Query(format!(
+                "Table '{}' not found. Run '{}' first to fetch and cache the data.",
+                t.name, t.command
+            )));
+        }
+
+        if let Some(t) = ALL_TABLES.iter().find(|t| {
+            self.snapshot_files(t).is_empty() && missing(&format!("{}{}", t.name, HISTORY_SUFFIX))
+        }) {
+            return Some(AppError::Query(format!(
+                "Table '{}{}' has no snapshots yet. Snapshots are recorded by '{}'.",
+                t.name, HISTORY_SUFFIX, t.command
+            )));
+        }
+
+        if let Some(view) = ALL_VIEWS
+            .iter()
+            .find(|v| !self.view_available(v) && missing(v.name))
+        {
+            let tables: Vec<&str> = view.requires.iter().map(|t| t.name).collect();
+            return Some(AppError::Query(format!(
+                "View '{}' needs the {} tables. Run 'which-llm refresh' first to fetch and cache the data.",
+                view.name,
+                tables.join(", ")
+            )));
+        }
+
+        self.view_errors
+            .iter()
+            .find(|(name, _)| missing(name))
+            .map(|(name, reason)| {
+                AppError::Query(format!(
+                    "View '{}' could not be registered: {}",
+                    name, reason
+                ))
+            })
+    }
+
+    /// List available tables with their schemas.
+    pub fn list_tables(&self) -> Vec<TableInfo> {
+        let mut tables = Vec::new();
+
+        for table_def in ALL_TABLES {
+            let parquet_path = self.cache_dir.join(table_def.parquet_file);
+            let exists = parquet_path.exists();
+
+            tables.push(TableInfo {
+                name: table_def.name.to_string(),
+                exists,
+                schema: Some(
+                    table_def
+                        .columns
+                        .iter()
+                        .map(|col| ColumnInfo {
+                            name: col.name.to_string(),
+                            data_type: col.sql_type.to_string(),
+                            nullable: col.nullable,
+                        })
+                        .collect(),
+                ),
+                view_sql: None,
+            });
+        }
+
+        for table_def in ALL_TABLES {
+            if self.snapshot_files(table_def).is_empty() {
+                continue;
+            }
+            let name = format!("{}{}", table_def.name, HISTORY_SUFFIX);
+            let exists = !self.view_errors.contains_key(&name);
+            tables.push(TableInfo {
+                schema: exists.then(|| self.view_columns(&name)),
+                name,
+                exists,
+                view_sql: None,
+            });
+        }
+
+        for view in ALL_VIEWS {
+            let exists = self.view_available(view) && !self.view_errors.contains_key(view.name);
+            tables.push(TableInfo {
+                name: view.name.to_string(),
+                exists,
+                schema: exists.then(|| self.view_columns(view.name)),
+                view_sql: None,
+            });
+        }
+
+        for (name, sql) in &self.options.views {
+            let exists = !self.view_errors.contains_key(name);
+            tables.push(TableInfo {
+                name: name.clone(),
+                exists,
+                schema: exists.then(|| self.view_columns(name)),
+                view_sql: Some(sql.clone()),
+            });
+        }
+
+        tables
+    }
+
+    /// Get the columns of a registered view.
+    fn view_columns(&self, name: &str) -> Vec<ColumnInfo> {
+        let query = || -> duckdb::Result<Vec<ColumnInfo>> {
+            let mut stmt = self.conn.prepare(
+                "SELECT column_name, data_type, is_nullable = 'YES' \
+                 FROM information_schema.columns WHERE table_name = ? ORDER BY ordinal_position",
+            )?;
+            let rows = stmt.query_map([name], |row| {
+                Ok(ColumnInfo {
+                    name: row.get(0)?,
+                    data_type: row.get(1)?,
+                    nullable: row.get(2)?,
+                })
+            })?;
+            rows.collect()
+        };
+        query().unwrap_or_default()
+    }
+}
+
+/// Convert Arrow record batches to a typed query result.
+fn batches_to_result(batches: QueryBatches) -> QueryResult {
+    let QueryBatches {
+        schema,
+        batches,
+        truncated,
+    } = batches;
+
+    if batches.is_empty() {
+        return QueryResult {
+            columns: vec![],
+            rows: vec![],
+            truncated,
+        };
+    }
+
+    let columns: Vec<String> = schema.fields().iter().map(|f| f.name().clone()).collect();
+
+    // Convert Arrow arrays to typed cells
+    let mut rows = Vec::new();
+    for batch in &batches {
+        let num_rows = batch.num_rows();
+        for row_idx in 0..num_rows {
+            let mut row_values = Vec::new();
+            for col_idx in 0..batch.num_columns() {
+                let col = batch.column(col_idx);
+                row_values.push(CellValue::from_arrow(col.as_ref(), row_idx));
+            }
+            rows.push(row_values);
+        }
+    }
+
+    QueryResult {
+        columns,
+        rows,
+        truncated,
+    }
+}
+
+/// Build the `CREATE VIEW` statement exposing a Parquet file under a table name.
+fn create_view_sql(name: &str, parquet_path: &Path) -> String {
+    format!(
+        "CREATE OR REPLACE VIEW {} AS SELECT * FROM read_parquet('{}')",
+        name,
+        sql_path(parquet_path)
+    )
+}
+
+/// Format a path for use inside a single-quoted SQL string literal.
+fn sql_path(path: &Path) -> String {
+    path.to_string_lossy()
+        .replace('\\', "/")
+        .replace('\'', "''")
+}
+
+/// Get the lowercased identifiers in a SQL string, skipping string literals
+/// and comments.
+fn sql_identifiers(sql: &str) -> Vec<String> {
+    let mut identifiers = Vec::new();
+    let mut chars = sql.chars().peekable();
+    let mut current = String::new();
+
+    while let Some(c) = chars.next() {
+        if c.is_alphanumeric() || c == '_' {
+            current.push(c.to_ascii_lowercase());
+            continue;
+        }
+        if !current.is_empty() {
+            identifiers.push(std::mem::take(&mut current));
+        }
+        match (c, chars.peek()) {
+            ('\'', _) => {
+                for c in chars.by_ref() {
+                    if c == '\'' {
+                        break;
+                    }
+                }
+            }
+            ('-', Some('-')) => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            }
+            ('/', Some('*')) => {
+                chars.next();
+                let mut prev = ' ';
+                for c in chars.by_ref() {
+                    if prev == '*' && c == '/' {
+                        break;
+                    }
+                    prev = c;
+                }
+            }
+            _ => {}
+        }
+    }
+    if !current.is_empty() {
+        identifiers.push(current);
+    }
+
+    identifiers
+}
+
+/// Execute a DuckDB query and return the results as Arrow RecordBatches.
+///
+/// Enforces the row cap and timeout from `options`.
+fn execute_duckdb_query(
+    conn: &Connection,
+    sql: &str,
+    values: &[Value],
+    options: &QueryOptions,
+) -> Result<QueryBatches> {
+    let watchdog = options
+        .timeout
+        .map(|timeout| limits::Watchdog::start(conn, timeout));
+    let result = fetch_batches(conn, sql, values, options.max_rows, watchdog.as_ref());
+    let timed_out = watchdog.map(limits::Watchdog::stop).unwrap_or(false);
+
+    // Interrupted before any rows came back: nothing to show
+    match (result, options.timeout) {
+        (Err(_), Some(timeout)) if timed_out => Err(AppError::Query(format!(
+            "Query timed out after {}s and was cancelled. Narrow the query or raise --timeout.",
+            timeout.as_secs()
+        ))),
+        (result, _) => result,
+    }
+}
+
+/// Leading keywords of statements that can be wrapped in a subquery.
+const ROW_STATEMENTS: &[&str] = &[
+    "SELECT", "WITH", "FROM", "VALUES", "TABLE", "PIVOT", "UNPIVOT",
+];
+
+/// Limit a query to `limit` rows, so DuckDB never builds more of the result.
+///
+/// Statements that can't be a subquery (e.g. `DESCRIBE`) are left alone.
+fn limit_sql(sql: &str, limit: usize) -> Option<String> {
+    let keyword = sandbox::leading_keyword(sql).to_uppercase();
+    if !ROW_STATEMENTS.contains(&keyword.as_str()) {
+        return None;
+    }
+    let body = sql.trim_end().trim_end_matches(';');
+    Some(format!("SELECT * FROM ({}\n) LIMIT {}", body, limit))
+}
+
+/// Run a statement and collect its batches, limited to `max_rows` rows.
+///
+/// If `watchdog` interrupts the query while its rows are fetched, the
+/// batches so far are returned, marked as truncated.
+fn fetch_batches(
+    conn: &Connection,
+    sql: &str,
+    values: &[Value],
+    max_rows: Option<usize>,
+    watchdog: Option<&limits::Watchdog>,
+) -> Result<QueryBatches> {
+    // One extra row tells whether the result was truncated. If the wrapped
+    // statement doesn't parse, run it as written and truncate while fetching.
+    let limited = max_rows
+        .and_then(|max| limit_sql(sql, max.saturating_add(1)))
+        .and_then(|limited| conn.prepare(&limited).ok());
+    let stmt = match limited {
+        Some(stmt) => Ok(stmt),
+        None => conn.prepare(sql),
+    };
+    let mut stmt = stmt.map_err(|e| {
+        // Provide helpful error messages for common mistakes
+        let msg = e.to_string();
+        if msg.contains("Permission Error") {
+            AppError::Sandbox(msg)
+        } else if msg.contains("syntax error") {
+            AppError::Query(format!("SQL syntax error: {}", msg))
+        } else if let Some(err) = suggest::unknown_name_error(conn, &msg) {
+            err
+        } else if msg.contains("does not exist") || msg.contains("not found") {
+            AppError::Query(format!(
+                "Table or column not found. Use 'which-llm tables' to see available tables and columns.\nError: {}",
+                msg
+            ))
+        } else {
+            AppError::Query(format!("SQL error: {}", msg))
+        }
+    })?;
+
+    // Use query_arrow to get results as Arrow RecordBatches
+    let arrow = stmt
+        .query_arrow(duckdb::params_from_iter(values))
+        .map_err(|e| AppError::Query(format!("Query error: {}", e)))?;
+    let schema = arrow.get_schema();
+
+    let mut batches = Vec::new();
+    let mut total = 0;
+    for batch in arrow {
+        if let Some(max) = max_rows {
+            if total + batch.num_rows() > max {
+                batches.push(batch.slice(0, max - total));
+                return Ok(QueryBatches {
+                    schema,
+                    batches,
+                    truncated: Some(Truncation::MaxRows(max)),
+                });
+            }
+        }
+        total += batch.num_rows();
+        batches.push(batch);
+    }
+
+    // DuckDB ends the stream early when interrupted
+    Ok(QueryBatches {
+        schema,
+        batches,
+        truncated: watchdog.and_then(limits::Watchdog::truncation),
+    })
+}
+
+/// Information about a table.
+pub struct TableInfo {
+    pub name: String,
+    pub exists: bool,
+    pub schema: Option<Vec<ColumnInfo>>,
+    /// The defining SELECT for user-defined views from config.
+    pub view_sql: Option<String>,
+}
+
+impl TableInfo {
+    /// Get the status shown by `which-llm tables`.
+    pub fn status(&self) -> &'static str {
+        let builtin_view = ALL_VIEWS.iter().any(|v| v.name == self.name);
+        match (&self.view_sql, builtin_view, self.exists) {
+            (Some(_), _, true) => "view from config",
+            (Some(_), _, false) => "view from config, unavailable",
+            (None, true, true) => "view",
+            (None, true, false) => "view, not available",
+            (None, false, true) => "cached",
+            (None, false, false) => "not cached",
+        }
+    }
+}
+
+/// Information about a column.
+pub struct ColumnInfo {
+    pub name: String,
+    pub data_type: String,
+    pub nullable: bool,
+}
+
+/// Format query results according to the output format.
+///
+/// `precision` sets the number of decimal places for floats in the
+/// human-readable formats; JSON and CSV always keep full precision.
+pub fn format_query_result(result: &QueryResult, format: OutputFormat, precision: usize) -> String {
+    if result.is_empty() {
+        return "No results.".to_string();
+    }
+
+    match format {
+        OutputFormat::Json => format_json(result),
+        OutputFormat::Csv => format_csv(result),
+        OutputFormat::Table => format_ascii_table(result, precision),
+        OutputFormat::Plain => format_plain(result, precision),
+        OutputFormat::Markdown => format_markdown(result, precision),
+    }
+}
+
+/// A result row serialized as a JSON object with keys in column order.
+struct JsonRow<'a> {
+    columns: &'a [String],
+    values: &'a [CellValue],
+}
+
+impl Serialize for JsonRow<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.columns.len()))?;
+        for (i, col) in self.columns.iter().enumerate() {
+            map.serialize_entry(col, self.values.get(i).unwrap_or(&CellValue::Null))?;
+        }
+        map.end()
+    }
+}
+
+fn format_json(result: &QueryResult) -> String {
+    let rows_json: Vec<JsonRow> = result
+        .rows
+        .iter()
+        .map(|row| JsonRow {
+            columns: &result.columns,
+            values: row,
+        })
+        .collect();
+    serde_json::to_string_pretty(&rows_json).unwrap_or_else(|_| "Error formatting JSON".to_string())
+}
+
+fn format_csv(result: &QueryResult) -> String {
+    let mut wtr = csv::Writer::from_writer(vec![]);
+    let _ = wtr.write_record(&result.columns);
+    for row in &result.rows {
+        let _ = wtr.write_record(row.iter().map(CellValue::to_text));
+    }
+    wtr.into_inner()
+        .map(|v| String::from_utf8_lossy(&v).to_string())
+        .unwrap_or_else(|_| "Error formatting CSV".to_string())
+}
+
+/// Render a row for the human-readable formats.
+fn display_row(row: &[CellValue], precision: usize) -> Vec<String> {
+    row.iter().map(|v| v.display(precision)).collect()
+}
+
+fn format_ascii_table(result: &QueryResult, precision: usize) -> String {
+    let mut table = Table::new();
+    table.load_preset(ASCII_BORDERS_ONLY_CONDENSED);
+    table.set_header(&result.columns);
+
+    for row in &result.rows {
+        table.add_row(display_row(row, precision));
+    }
+
+    table.to_string()
+}
+
+fn format_plain(result: &QueryResult, precision: usize) -> String {
+    let mut output = String::new();
+    for row in &result.rows {
+        output.push_str(&display_row(row, precision).join("\t"));
+        output.push('\n');
+    }
+    output
+}
+
+fn format_markdown(result: &QueryResult, precision: usize) -> String {
+    use std::fmt::Write;
+
+    let mut output = String::new();
+
+    // Header row
+    write!(output, "| {} |", result.columns.join(" | ")).unwrap();
+    writeln!(output).unwrap();
+
+    // Separator row
+    let separators: Vec<&str> = result.columns.iter().map(|_| "---").collect();
+    write!(output, "| {} |", separators.join(" | ")).unwrap();
+    writeln!(output).unwrap();
+
+    // Data rows
+    for row in &result.rows {
+        write!(output, "| {} |", display_row(row, precision).join(" | ")).unwrap();
+        writeln!(output).unwrap();
+    }
+
+    output
+}
+
+/// Format table list for display.
+pub fn format_tables_list(tables: &[TableInfo]) -> String {
+    use std::fmt::Write;
+
+    let mut output = String::new();
+
+    writeln!(output, "Available tables:\n").unwrap();
+
+    for table in tables {
+        writeln!(output, "  {} ({})", table.name, table.status()).unwrap();
+
+        if let Some(ref schema) = table.schema {
+            writeln!(output, "    Columns:").unwrap();
+            for col in schema {
+                let nullable = if col.nullable { "NULL" } else { "NOT NULL" };
+                writeln!(
+                    output,
+                    "      - {} {} {}",
+                    col.name, col.data_type, nullable
+                )
+                .unwrap();
+            }
+        }
+        writeln!(output).unwrap();
+    }
+
+    writeln!(output, "To cache a table, run the corresponding command:").unwrap();
+    for table_def in ALL_TABLES {
+        writeln!(output, "  {} -> {}", table_def.command, table_def.name).unwrap();
+    }
+
+    writeln!(output, "\nBuilt-in macros:").unwrap();
+    for m in macros::MACROS {
+        writeln!(output, "  {}\n      {}", m.signature, m.description).unwrap();
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// Write a small benchmarks Parquet file into the cache directory.
+    fn write_benchmarks(dir: &Path) {
+        let path = dir.join("benchmarks.parquet");
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(&format!(
+            "COPY (SELECT * FROM (VALUES ('gpt-4o', 'openai', 55.0), ('claude', 'anthropic', 60.0), \
+             ('gpt-4o-mini', 'openai', 40.0)) t(slug, creator_slug, intelligence)) \
+             TO '{}' (FORMAT PARQUET)",
+            path.to_string_lossy().replace('\\', "/")
+        ))
+        .unwrap();
+    }
+
+    #[test]
+    fn test_query_registered_view() {
+        let temp_dir = TempDir::new().unwrap();
+        write_benchmarks(temp_dir.path());
+        let executor = QueryExecutor::new(temp_dir.path().to_path_buf()).unwrap();
+
+        let result = executor
+            .execute("SELECT slug FROM benchmarks WHERE intelligence > 50 ORDER BY slug")
+            .unwrap();
+
+        assert_eq!(result.columns, vec!["slug"]);
+        assert_eq!(result.len(), 2);
+        assert_eq!(result.rows[0][0], CellValue::from("claude"));
+    }
+
+    #[test]
+    fn test_query_missing_table() {
+        let temp_dir = TempDir::new().unwrap();
+        let executor = QueryExecutor::new(temp_dir.path().to_path_buf()).unwrap();
+
+        let result = executor.execute("SELECT * FROM benchmarks");
+
+        assert!(result.is_err());
+        let err = result.unwrap_err().to_string();
+        assert!(err.contains("not found"));
+        assert!(err.contains("which-llm refresh"));
+    }
+
+    #[test]
+    fn test_query_unknown_column_suggestions() {
+        let temp_dir = TempDir::new().unwrap();
+        write_benchmarks(temp_dir.path());
+        let executor = QueryExecutor::new(temp_dir.path().to_path_buf()).unwrap();
+
+        let err = executor
+            .execute("SELECT inteligence FROM benchmarks")
+            .unwrap_err();
+
+        assert!(matches!(err, AppError::UnknownName { kind: "column", .. }));
+        assert!(err.to_string().contains("Did you mean 'intelligence'"));
+    }
+
+    #[test]
+    fn test_query_with_params() {
+        let temp_dir = TempDir::new().unwrap();
+        write_benchmarks(temp_dir.path());
+        let executor = QueryExecutor::new(temp_dir.path().to_path_buf()).unwrap();
+
+        let params = vec![
+            ("creator".to_string(), "openai".to_string()),
+            ("min".to_string(), "50".to_string()),
+        ];
+        let result = executor
+            .execute_with_params(
+                "SELECT slug FROM benchmarks WHERE creator_slug = :creator AND intelligence > :min",
+                &params,
+            )
+            .unwrap();
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result.rows[0][0], CellValue::from("gpt-4o"));
+
+        // Values are bound, not spliced into the SQL
+        let params = vec![("creator".to_string(), "x' OR 1=1 --".to_string())];
+        let result = executor
+            .execute_with_params(
+                "SELECT slug FROM benchmarks WHERE creator_slug = :creator",
+                &params,
+            )
+            .unwrap();
+        assert!(result.is_empty());
+
+        // Values that look numeric keep their text when compared with text
+        let params = vec![
+            ("zip".to_string(), "007".to_string()),
+            ("x".to_string(), "nan".to_string()),
+        ];
+        let result = executor
+            .execute_with_params("SELECT :zip = '007', :x = 'nan'", &params)
+            .unwrap();
+        assert_eq!(result.rows[0][0], CellValue::Bool(true));
+        assert_eq!(result.rows[0][1], CellValue::Bool(true));
+    }
+
+    #[test]
+    fn test_query_string_literal_and_cte_not_rewritten() {
+        let temp_dir = TempDir::new().unwrap();
+        write_benchmarks(temp_dir.path());
+        let executor = QueryExecutor::new(temp_dir.path().to_path_buf()).unwrap();
+
+        // Table names inside string literals are left alone
+        let result = executor
+            .execute("SELECT 'benchmarks' AS table_name FROM benchmarks LIMIT 1")
+            .unwrap();
+        assert_eq!(result.rows[0][0], CellValue::from("benchmarks"));
+
+        // A CTE may shadow a table name
+        let result = executor
+            .execute("WITH models AS (SELECT 1 AS n) SELECT n FROM models")
+            .unwrap();
+        assert_eq!(result.rows[0][0], CellValue::Int(1));
+    }
+
+    #[test]
+    fn test_query_duckdb_syntax() {
+        let temp_dir = TempDir::new().unwrap();
+        write_benchmarks(temp_dir.path());
+        let executor = QueryExecutor::new(temp_dir.path().to_path_buf()).unwrap();
+
+        // FROM-first
+        let result = executor.execute("FROM benchmarks SELECT slug").unwrap();
+        assert_eq!(result.len(), 3);
+
+        // QUALIFY
+        let result = executor
+            .execute(
+                "SELECT creator_slug, slug FROM benchmarks \
+                 QUALIFY row_number() OVER (PARTITION BY creator_slug ORDER BY intelligence DESC) = 1 \
+                 ORDER BY creator_slug",
+            )
+            .unwrap();
+        assert_eq!(result.len(), 2);
+        assert_eq!(result.rows[1][1], CellValue::from("gpt-4o"));
+
+        // SUMMARIZE
+        let result = executor.execute("SUMMARIZE benchmarks").unwrap();
+        assert_eq!(result.len(), 3);
+    }
+
+    #[test]
+    fn test_list_tables() {
+        let temp_dir = TempDir::new().unwrap();
+
+        // Create one parquet file
+        write_benchmarks(temp_dir.path());
+
+        let executor = QueryExecutor::new(temp_dir.path().to_path_buf()).unwrap();
+        let tables = executor.list_tables();
+
+        // Every table plus the built-in llms view
+        assert_eq!(tables.len(), 9);
+
+        let benchmarks = tables.iter().find(|t| t.name == "benchmarks").unwrap();
+        assert!(benchmarks.exists);
+
+        let text_to_image = tables.iter().find(|t| t.name == "text_to_image").unwrap();
+        assert!(!text_to_image.exists);
+    }
+
+    #[test]
+    fn test_query_typed_values() {
+        let temp_dir = TempDir::new().unwrap();
+        let executor = QueryExecutor::new(temp_dir.path().to_path_buf()).unwrap();
+
+        let result = executor
+            .execute("SELECT 0.075::DOUBLE AS price, 42 AS n, true AS flag, NULL AS missing")
+            .unwrap();
+
+        assert_eq!(
+            result.rows[0],
+            vec![
+                CellValue::Float(0.075),
+                CellValue::Int(42),
+                CellValue::Bool(true),
+                CellValue::Null,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_query_temporal_and_nested_values() {
+        let temp_dir = TempDir::new().unwrap();
+        let executor = QueryExecutor::new(temp_dir.path().to_path_buf()).unwrap();
+
+        let result = executor
+            .execute(
+                "SELECT DATE '2024-05-13' AS d, TIMESTAMP '2024-05-13 09:30:00' AS ts, \
+                 ['a', 'b'] AS ids, {'id': 1, 'ok': true} AS info",
+            )
+            .unwrap();
+        let row: Vec<String> = result.rows[0].iter().map(CellValue::to_text).collect();
+
+        assert_eq!(row[0], "2024-05-13");
+        assert_eq!(row[1], "2024-05-13T09:30:00");
+        assert_eq!(row[2], r#"["a","b"]"#);
+        assert_eq!(row[3], r#"{"id":1,"ok":true}"#);
+
+        let json = format_query_result(&result, OutputFormat::Json, DEFAULT_PRECISION);
+        assert!(json.contains(r#""d": "2024-05-13""#));
+        assert!(json.contains(r#""id": 1"#));
+    }
+
+    #[test]
+    fn test_query_builtin_macros() {
+        let temp_dir = TempDir::new().unwrap();
+        write_benchmarks(temp_dir.path());
+        let executor = QueryExecutor::new(temp_dir.path().to_path_buf()).unwrap();
+
+        let result = executor
+            .execute(
+                "SELECT slug, intelligence_per_dollar(intelligence, 2.0) AS ipd \
+                 FROM benchmarks ORDER BY ipd DESC LIMIT 1",
+            )
+            .unwrap();
+
+        assert_eq!(result.rows[0][0], CellValue::from("claude"));
+        assert_eq!(result.rows[0][1], CellValue::Float(30.0));
+    }
+
+    #[test]
+    fn test_explain() {
+        let temp_dir = TempDir::new().unwrap();
+        write_benchmarks(temp_dir.path());
+        let executor = QueryExecutor::new(temp_dir.path().to_path_buf()).unwrap();
+
+        let output = executor
+            .explain("SELECT slug FROM benchmarks WHERE intelligence > 50", &[])
+            .unwrap();
+
+        assert!(output.contains("SELECT slug FROM benchmarks WHERE intelligence > 50"));
+        assert!(output.contains("CREATE OR REPLACE VIEW benchmarks AS SELECT * FROM read_parquet("));
+        assert!(!output.contains("VIEW models"));
+        assert!(output.contains("-- Plan"));
+
+        let err = executor.explain("SELECT * FROM models", &[]).unwrap_err();
+        assert!(err.to_string().contains("which-llm refresh"));
+    }
+
+    #[test]
+    fn test_sql_identifiers() {
+        assert_eq!(
+            sql_identifiers("SELECT 'models' AS t -- from models\nFROM Benchmarks /* llms */"),
+            vec!["select", "as", "t", "from", "benchmarks"]
+        );
+    }
+
+    #[test]
+    fn test_sandbox_blocks_writes_and_file_access() {
+        let temp_dir = TempDir::new().unwrap();
+        write_benchmarks(temp_dir.path());
+        let executor = QueryExecutor::new(temp_dir.path().to_path_buf()).unwrap();
+
+        // Registered tables remain readable
+        assert!(executor.execute("SELECT count(*) FROM benchmarks").is_ok());
+
+        let out = temp_dir.path().join("out.csv");
+        for sql in [
+            format!("COPY benchmarks TO '{}'", out.to_string_lossy()),
+            "INSTALL httpfs".to_string(),
+            "SET enable_external_access = true".to_string(),
+            "SELECT * FROM read_csv('/etc/passwd')".to_string(),
+        ] {
+            let err = executor.execute(&sql).unwrap_err();
+            assert!(matches!(err, AppError::Sandbox(_)), "{sql}: {err}");
+        }
+        assert!(!out.exists());
+    }
+
+    #[test]
+    fn test_sandbox_allows_temp_views_between_statements() {
+        let temp_dir = TempDir::new().unwrap();
+        write_benchmarks(temp_dir.path());
+        let executor = QueryExecutor::new(temp_dir.path().to_path_buf()).unwrap();
+
+        executor
+            .execute(
+                "CREATE TEMP VIEW openai AS SELECT * FROM benchmarks WHERE creator_slug = 'openai'",
+            )
+            .unwrap();
+        let result = executor.execute("SELECT count(*) FROM openai").unwrap();
+
+        assert_eq!(result.rows[0][0], CellValue::Int(2));
+    }
+
+    #[test]
+    fn test_user_views() {
+        let temp_dir = TempDir::new().unwrap();
+        write_benchmarks(temp_dir.path());
+        let views = BTreeMap::from([
+            (
+                "openai".to_string(),
+                "SELECT * FROM benchmarks WHERE creator_slug = 'openai'".to_string(),
+            ),
+            // Depends on another user view
+            (
+                "openai_top".to_string(),
+                "SELECT slug FROM openai ORDER BY intelligence DESC LIMIT 1;".to_string(),
+            ),
+            ("broken".to_string(), "SELECT * FROM models".to_string()),
+            ("benchmarks".to_string(), "SELECT 1".to_string()),
+            // Chained statements must not run
+            (
+                "escape".to_string(),
+                format!(
+                    "SELECT 1; COPY (SELECT 42) TO '{}'",
+                    temp_dir.path().join("escaped.csv").display()
+                ),
+            ),
+        ]);
+        let options = QueryOptions {
+            views,
+            ..Default::default()
+        };
+        let executor = QueryExecutor::with_options(temp_dir.path().to_path_buf(), options).unwrap();
+
+        let result = executor.execute("SELECT * FROM openai_top").unwrap();
+        assert_eq!(result.rows[0][0], CellValue::from("gpt-4o"));
+
+        let err = executor.execute("SELECT * FROM broken").unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("View 'broken' could not be registered"));
+
+        let tables = executor.list_tables();
+        let openai = tables.iter().find(|t| t.name == "openai").unwrap();
+        assert!(openai.exists && openai.view_sql.is_some());
+        assert!(openai
+            .schema
+            .as_ref()
+            .unwrap()
+            .iter()
+            .any(|c| c.name == "slug"));
+        assert!(!tables.iter().find(|t| t.name == "broken").unwrap().exists);
+        // Built-in tables cannot be shadowed
+        assert!(tables
+            .iter()
+            .any(|t| t.name == "benchmarks" && t.view_sql.is_some() && !t.exists));
+        let result = executor.execute("SELECT count(*) FROM benchmarks").unwrap();
+        assert_eq!(result.rows[0][0], CellValue::Int(3));
+
+        let err = executor.execute("SELECT * FROM escape").unwrap_err();
+        assert!(err.to_string().contains("single SELECT statement"));
+        assert!(!temp_dir.path().join("escaped.csv").exists());
+    }
+
+    #[test]
+    fn test_llms_view() {
+        let temp_dir = TempDir::new().unwrap();
+        let executor = QueryExecutor::new(temp_dir.path().to_path_buf()).unwrap();
+        let err = executor.execute("SELECT * FROM llms").unwrap_err();
+        assert!(err.to_string().contains("Run 'which-llm refresh'"));
+
+        let path = |file: &str| {
+            temp_dir
+                .path()
+                .join(file)
+                .to_string_lossy()
+                .replace('\\', "/")
+        };
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(&format!(
+            "COPY (SELECT * FROM (VALUES ('1', 'gpt-4o', 55.0), ('2', 'mystery', 40.0)) \
+             t(id, slug, intelligence)) TO '{}' (FORMAT PARQUET);
+             COPY (SELECT * FROM (VALUES \
+               ('openai', 'gpt-4o', true, true, false, true, false, 128000, 16384, 'text,image', 'text'), \
+               ('azure', 'gpt-4o', false, true, false, true, false, 64000, 4096, 'text', 'text')) \
+             t(provider_id, model_id, tool_call, structured_output, reasoning, attachment, \
+               open_weights, context_window, max_output_tokens, input_modalities, output_modalities)) \
+             TO '{}' (FORMAT PARQUET);
+             COPY (SELECT * FROM (VALUES ('1', 'gpt-4o', 'openai', 'gpt-4o', 1.0, 'exact'), \
+               ('1', 'gpt-4o', 'azure', 'gpt-4o', 0.9, 'reordered')) \
+             t(aa_id, aa_slug, provider_id, model_id, confidence, method)) TO '{}' (FORMAT PARQUET);",
+            path("benchmarks.parquet"),
+            path("models.parquet"),
+            path("crosswalk.parquet"),
+        ))
+        .unwrap();
+
+        let executor = QueryExecutor::new(temp_dir.path().to_path_buf()).unwrap();
+        let result = executor
+            .execute(
+                "SELECT slug, tool_call, context_window, input_modalities, providers, \
+                 models_dev_matched FROM llms ORDER BY slug",
+            )
+            .unwrap();
+        assert_eq!(result.len(), 2);
+        assert_eq!(result.rows[0][1], CellValue::Bool(true));
+        assert_eq!(result.rows[0][2], CellValue::Int(128000));
+        assert_eq!(result.rows[0][3], CellValue::from("text,image"));
+        assert_eq!(result.rows[0][5], CellValue::Bool(true));
+        // Unlinked models keep their benchmarks with NULL capabilities
+        assert_eq!(result.rows[1][1], CellValue::Null);
+        assert_eq!(result.rows[1][5], CellValue::Bool(false));
+
+        let llms = executor
+            .list_tables()
+            .into_iter()
+            .find(|t| t.name == "llms")
+            .unwrap();
+        assert_eq!(llms.status(), "view");
+        assert!(llms
+            .schema
+            .unwrap()
+            .iter()
+            .any(|c| c.name == "match_confidence"));
+    }
+
+    #[test]
+    fn test_history_and_as_of() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache_dir = temp_dir.path();
+        let date = |s: &str| history::parse_date(s).unwrap();
+
+        let executor = QueryExecutor::new(cache_dir.to_path_buf()).unwrap();
+        let err = executor
+            .execute("SELECT * FROM benchmarks_history")
+            .unwrap_err();
+        assert!(err.to_string().contains("has no snapshots yet"));
+
+        write_benchmarks(cache_dir);
+        history::save_snapshot(cache_dir, date("2026-08-01")).unwrap();
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(&format!(
+            "COPY (SELECT 'gpt-5' AS slug, 'openai' AS creator_slug, 70.0 AS intelligence) \
+             TO '{}' (FORMAT PARQUET)",
+            sql_path(&cache_dir.join("benchmarks.parquet"))
+        ))
+        .unwrap();
+        history::save_snapshot(cache_dir, date("2026-09-01")).unwrap();
+
+        let executor = QueryExecutor::new(cache_dir.to_path_buf()).unwrap();
+        let result = executor
+            .execute(
+                "SELECT snapshot_date::VARCHAR, count(*) FROM benchmarks_history \
+                 GROUP BY ALL ORDER BY 1",
+            )
+            .unwrap();
+        assert_eq!(result.len(), 2);
+        assert_eq!(result.rows[0][0], CellValue::from("2026-08-01"));
+        assert_eq!(result.rows[0][1], CellValue::Int(3));
+        assert!(executor
+            .list_tables()
+            .iter()
+            .any(|t| t.name == "benchmarks_history" && t.exists));
+
+        let options = QueryOptions {
+            as_of: Some(date("2026-08-15")),
+            ..Default::default()
+        };
+        let executor = QueryExecutor::with_options(cache_dir.to_path_buf(), options).unwrap();
+        let result = executor.execute("SELECT count(*) FROM benchmarks").unwrap();
+        assert_eq!(result.rows[0][0], CellValue::Int(3));
+        let result = executor
+            .execute("SELECT count(DISTINCT snapshot_date) FROM benchmarks_history")
+            .unwrap();
+        assert_eq!(result.rows[0][0], CellValue::Int(1));
+
+        let options = QueryOptions {
+            as_of: Some(date("2026-01-01")),
+            ..Default::default()
+        };
+        assert!(QueryExecutor::with_options(cache_dir.to_path_buf(), options).is_err());
+    }
+
+    #[test]
+    fn test_sandbox_disabled() {
+        let temp_dir = TempDir::new().unwrap();
+        write_benchmarks(temp_dir.path());
+        let options = QueryOptions {
+            sandbox: false,
+            ..Default::default()
+        };
+        let executor = QueryExecutor::with_options(temp_dir.path().to_path_buf(), options).unwrap();
+
+        let result = executor.execute("CREATE TEMP TABLE t AS SELECT 1 AS n");
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_query_max_rows() {
+        let temp_dir = TempDir::new().unwrap();
+        let options = QueryOptions {
+            max_rows: Some(10),
+            ..Default::default()
+        };
+        let executor = QueryExecutor::with_options(temp_dir.path().to_path_buf(), options).unwrap();
+
+        let result = executor.execute("SELECT * FROM range(100000)").unwrap();
+        assert_eq!(result.len(), 10);
+        assert_eq!(result.truncated, Some(Truncation::MaxRows(10)));
+
+        let result = executor.execute("SELECT * FROM range(10)").unwrap();
+        assert_eq!(result.len(), 10);
+        assert_eq!(result.truncated, None);
+
+        // A runaway cross join stops at the limit
+        let result = executor
+            .execute("SELECT * FROM range(1000000000) r, range(1000000000) s;")
+            .unwrap();
+        assert_eq!(result.truncated, Some(Truncation::MaxRows(10)));
+        assert!(limit_sql("DESCRIBE benchmarks", 11).is_none());
+
+        // The extra row doesn't overflow
+        let options = QueryOptions {
+            max_rows: Some(usize::MAX),
+            ..Default::default()
+        };
+        let executor = QueryExecutor::with_options(temp_dir.path().to_path_buf(), options).unwrap();
+        let result = executor.execute("SELECT * FROM range(10)").unwrap();
+        assert_eq!(result.len(), 10);
+        assert_eq!(result.truncated, None);
+    }
+
+    #[test]
+    fn test_query_timeout() {
+        let temp_dir = TempDir::new().unwrap();
+        let options = QueryOptions {
+            timeout: Some(Duration::from_millis(200)),
+            ..Default::default()
+        };
+        let executor = QueryExecutor::with_options(temp_dir.path().to_path_buf(), options).unwrap();
+
+        let result = executor.execute(
+            "SELECT count(*) FROM range(100000000) a, range(100000000) b WHERE a.range + b.range = 3",
+        );
+        let err = result.unwrap_err().to_string();
+        assert!(err.contains("timed out"), "{err}");
+    }
+
+    #[test]
+    fn test_query_memory_limit() {
+        let temp_dir = TempDir::new().unwrap();
+        let options = QueryOptions {
+            memory_limit: Some("256MB".into()),
+            ..Default::default()
+        };
+        let executor = QueryExecutor::with_options(temp_dir.path().to_path_buf(), options).unwrap();
+        assert!(executor
+            .execute("SELECT current_setting('memory_limit')")
+            .is_ok());
+
+        let options = QueryOptions {
+            memory_limit: Some("lots".into()),
+            ..Default::default()
+        };
+        assert!(QueryExecutor::with_options(temp_dir.path().to_path_buf(), options).is_err());
+    }
+
+    #[test]
+    fn test_format_query_result_markdown() {
+        let result = QueryResult {
+            columns: vec!["name".to_string(), "score".to_string()],
+            rows: vec![
+                vec!["Model A".into(), CellValue::Int(100)],
+                vec!["Model B".into(), CellValue::Int(95)],
+            ],
+            truncated: None,
+        };
+
+        let output = format_query_result(&result, OutputFormat::Markdown, DEFAULT_PRECISION);
+        assert!(output.contains("| name | score |"));
+        assert!(output.contains("| Model A | 100 |"));
+    }
+
+    #[test]
+    fn test_format_query_result_json() {
+        let result = QueryResult {
+            columns: vec!["name".to_string(), "price".to_string(), "note".to_string()],
+            rows: vec![vec![
+                "Test".into(),
+                CellValue::Float(0.075),
+                CellValue::Null,
+            ]],
+            truncated: None,
+        };
+
+        let output = format_query_result(&result, OutputFormat::Json, DEFAULT_PRECISION);
+        assert!(output.contains("\"name\": \"Test\""));
+        assert!(output.contains("\"price\": 0.075"));
+        assert!(output.contains("\"note\": null"));
+
+        // Keys keep column order
+        assert!(output.find("\"name\"").unwrap() < output.find("\"price\"").unwrap());
+    }
+
+    #[test]
+    fn test_format_query_result_precision() {
+        let result = QueryResult {
+            columns: vec!["price".to_string()],
+            rows: vec![vec![CellValue::Float(0.075)]],
+            truncated: None,
+        };
+
+        assert!(format_query_result(&result, OutputFormat::Markdown, 2).contains("| 0.07 |"));
+        assert!(format_query_result(&result, OutputFormat::Markdown, 3).contains("| 0.075 |"));
+        assert!(format_query_result(&result, OutputFormat::Csv, 0).contains("0.075"));
+    }
+
+    #[test]
+    fn test_format_query_result_table() {
+        let result = QueryResult {
+            columns: vec!["name".to_string(), "score".to_string()],
+            rows: vec![vec!["Model A".into(), CellValue::Int(100)]],
+            truncated: None,
+        };
+
+        let output = format_query_result(&result, OutputFormat::Table, DEFAULT_PRECISION);
+        assert!(output.contains("name"));
+        assert!(output.contains("score"));
+        assert!(output.contains("Model A"));
+        assert!(output.contains("100"));
+    }
+}
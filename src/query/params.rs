@@ -0,0 +1,228 @@
+//! Named query parameters.
+//!
+//! Queries may contain `:name` placeholders. They are rewritten to DuckDB's
+//! numbered parameters (`$1`, `$2`, ...) and the values are bound through the
+//! prepared statement, never spliced into the SQL text. Values are bound as
+//! text and DuckDB casts them to the type the query expects, so `007` stays
+//! `'007'` when compared with text but works as a number in `coding > :min`.
+
+use crate::error::{AppError, Result};
+use duckdb::types::Value;
+
+/// A query with its placeholders rewritten and values ready to bind.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoundQuery {
+    pub sql: String,
+    pub values: Vec<Value>,
+}
+
+/// Parse a `name=value` command-line parameter.
+pub fn parse_param(s: &str) -> std::result::Result<(String, String), String> {
+    let (name, value) = s
+        .split_once('=')
+        .ok_or_else(|| format!("expected NAME=VALUE, got '{}'", s))?;
+    let name = name.trim().trim_start_matches(':');
+    if name.is_empty() || !name.chars().all(is_name_char) {
+        return Err(format!("invalid parameter name '{}'", name));
+    }
+    Ok((name.to_string(), value.to_string()))
+}
+
+/// Get the distinct placeholder names in a query, in order of first use.
+pub fn placeholders(sql: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    scan(sql, |name| {
+        if !names.iter().any(|n| n == name) {
+            names.push(name.to_string());
+        }
+        String::new()
+    });
+    names
+}
+
+/// Rewrite `:name` placeholders and collect the values to bind.
+///
+/// Every placeholder needs a value and every value needs a placeholder.
+pub fn bind(sql: &str, params: &[(String, String)]) -> Result<BoundQuery> {
+    let names = placeholders(sql);
+
+    let missing: Vec<String> = names
+        .iter()
+        .filter(|n| !params.iter().any(|(p, _)| p == *n))
+        .map(|n| format!(":{}", n))
+        .collect();
+    if !missing.is_empty() {
+        return Err(AppError::Query(format!(
+            "Missing value for parameter {}. Pass it with --param NAME=VALUE.",
+            missing.join(", ")
+        )));
+    }
+
+    if let Some((unused, _)) = params.iter().find(|(p, _)| !names.contains(p)) {
+        return Err(AppError::Query(format!(
+            "Unknown parameter '{}'. The query uses: {}",
+            unused,
+            if names.is_empty() {
+                "no parameters".to_string()
+            } else {
+                names
+                    .iter()
+                    .map(|n| format!(":{}", n))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            }
+        )));
+    }
+
+    let sql = scan(sql, |name| {
+        let index = names.iter().position(|n| n == name).unwrap_or(0);
+        format!("${}", index + 1)
+    });
+    let values = names
+        .iter()
+        .map(|name| {
+            // The last occurrence wins when a parameter is given twice
+            let (_, value) = params.iter().rev().find(|(p, _)| p == name).unwrap();
+            Value::Text(value.clone())
+        })
+        .collect();
+
+    Ok(BoundQuery { sql, values })
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+/// Walk the SQL, replacing each `:name` placeholder with `replace(name)`.
+///
+/// String literals, quoted identifiers and comments are copied verbatim. A
+/// colon only starts a placeholder when it follows an operator, comma,
+/// parenthesis or whitespace, so `x::DOUBLE` casts, `list[1:n]` slices and
+/// `{'a':b}` struct literals are left alone.
+fn scan(sql: &str, mut replace: impl FnMut(&str) -> String) -> String {
+    let chars: Vec<char> = sql.chars().collect();
+    let mut out = String::with_capacity(sql.len());
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '\'' | '"' => {
+                // Quoted literal or identifier ('' and "" escape themselves)
+                let end = chars[i + 1..]
+                    .iter()
+                    .position(|&q| q == c)
+                    .map(|p| i + 1 + p)
+                    .unwrap_or(chars.len() - 1);
+                out.extend(&chars[i..=end]);
+                i = end + 1;
+            }
+            '-' if chars.get(i + 1) == Some(&'-') => {
+                let end = chars[i..]
+                    .iter()
+                    .position(|&ch| ch == '\n')
+                    .map(|p| i + p)
+                    .unwrap_or(chars.len());
+                out.extend(&chars[i..end]);
+                i = end;
+            }
+            '/' if chars.get(i + 1) == Some(&'*') => {
+                let end = (i + 2..chars.len().saturating_sub(1))
+                    .find(|&j| chars[j] == '*' && chars[j + 1] == '/')
+                    .map(|j| j + 2)
+                    .unwrap_or(chars.len());
+                out.extend(&chars[i..end]);
+                i = end;
+            }
+            ':' if starts_placeholder(&chars, i) => {
+                let end = chars[i + 1..]
+                    .iter()
+                    .position(|&ch| !is_name_char(ch))
+                    .map(|p| i + 1 + p)
+                    .unwrap_or(chars.len());
+                let name: String = chars[i + 1..end].iter().collect();
+                out.push_str(&replace(&name));
+                i = end;
+            }
+            _ => {
+                out.push(c);
+                i += 1;
+            }
+        }
+    }
+
+    out
+}
+
+/// Whether the colon at `i` starts a `:name` placeholder.
+fn starts_placeholder(chars: &[char], i: usize) -> bool {
+    let next_ok = chars
+        .get(i + 1)
+        .is_some_and(|c| c.is_ascii_alphabetic() || *c == '_');
+    let prev_ok = match i.checked_sub(1).map(|p| chars[p]) {
+        None => true,
+        Some(p) => !(is_name_char(p) || matches!(p, ':' | ')' | ']' | '\'' | '"' | '}')),
+    };
+    next_ok && prev_ok
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(n, v)| (n.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_param() {
+        assert_eq!(
+            parse_param("min_coding=40").unwrap(),
+            ("min_coding".to_string(), "40".to_string())
+        );
+        assert_eq!(
+            parse_param(":creator=Open=AI").unwrap(),
+            ("creator".to_string(), "Open=AI".to_string())
+        );
+        assert!(parse_param("min_coding").is_err());
+        assert!(parse_param("bad name=1").is_err());
+    }
+
+    #[test]
+    fn test_placeholders_skip_literals_casts_and_slices() {
+        let sql = "SELECT x::DOUBLE, l[1:n], {'a':b} FROM t \
+                   WHERE a > :min AND b = ':not' AND c IN (:min,:other) -- :comment";
+        assert_eq!(placeholders(sql), vec!["min", "other"]);
+    }
+
+    #[test]
+    fn test_bind() {
+        let bound = bind(
+            "SELECT * FROM benchmarks WHERE coding > :min_coding AND creator = :creator OR coding > :min_coding",
+            &params(&[("creator", "OpenAI"), ("min_coding", "40")]),
+        )
+        .unwrap();
+
+        assert_eq!(
+            bound.sql,
+            "SELECT * FROM benchmarks WHERE coding > $1 AND creator = $2 OR coding > $1"
+        );
+        assert_eq!(
+            bound.values,
+            vec![Value::Text("40".into()), Value::Text("OpenAI".into())]
+        );
+    }
+
+    #[test]
+    fn test_bind_errors() {
+        let err = bind("SELECT :a, :b", &params(&[("a", "1")])).unwrap_err();
+        assert!(err.to_string().contains("Missing value for parameter :b"));
+
+        let err = bind("SELECT :a", &params(&[("a", "1"), ("c", "2")])).unwrap_err();
+        assert!(err.to_string().contains("Unknown parameter 'c'"));
+    }
+}
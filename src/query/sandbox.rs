@@ -0,0 +1,163 @@
+//! Read-only sandbox for user-supplied SQL.
+//!
+//! Two layers keep `which-llm query` safe to hand to AI assistants:
+//! - Only read-only statements (SELECT, WITH, FROM-first, SUMMARIZE, ...) and
+//!   session-local `CREATE TEMP VIEW/TABLE` are accepted; everything else is
+//!   rejected before it reaches DuckDB.
+//! - The DuckDB connection is locked down: external file access is limited to
+//!   the cached Parquet files and extension loading is disabled.
+
+use crate::error::{AppError, Result};
+use std::path::PathBuf;
+
+/// Leading keywords of statements allowed in sandbox mode.
+const ALLOWED_STATEMENTS: &[&str] = &[
+    "SELECT",
+    "WITH",
+    "FROM",
+    "VALUES",
+    "TABLE",
+    "SUMMARIZE",
+    "DESCRIBE",
+    "SHOW",
+    "PIVOT",
+    "UNPIVOT",
+    "EXPLAIN",
+];
+
+/// Check that a statement is allowed in sandbox mode.
+pub fn check_statement(sql: &str) -> Result<()> {
+    let keyword = leading_keyword(sql).to_uppercase();
+
+    if keyword.is_empty() {
+        return Err(AppError::Sandbox("empty statement".into()));
+    }
+
+    if ALLOWED_STATEMENTS.contains(&keyword.as_str()) || is_temp_create(sql) {
+        Ok(())
+    } else {
+        Err(AppError::Sandbox(format!(
+            "{} statements are not allowed; only read-only queries ({}) and CREATE TEMP VIEW/TABLE can be run",
+            keyword,
+            ALLOWED_STATEMENTS.join(", ")
+        )))
+    }
+}
+
+/// Build the settings that lock down a connection.
+///
+/// Must run after the views are registered: once `lock_configuration` is set
+/// no further settings can be changed by user SQL.
+pub fn lockdown_sql(allowed_paths: &[PathBuf]) -> String {
+    let paths: Vec<String> = allowed_paths
+        .iter()
+        .map(|p| {
+            format!(
+                "'{}'",
+                p.to_string_lossy().replace('\\', "/").replace('\'', "''")
+            )
+        })
+        .collect();
+
+    format!(
+        "SET allowed_paths = [{}];
+         SET autoinstall_known_extensions = false;
+         SET autoload_known_extensions = false;
+         SET enable_external_access = false;
+         SET lock_configuration = true;",
+        paths.join(", ")
+    )
+}
+
+/// Whether a statement creates a temporary view or table, which only lives
+/// for the current session.
+fn is_temp_create(sql: &str) -> bool {
+    let words: Vec<String> = skip_leading_comments(sql)
+        .split_whitespace()
+        .take(5)
+        .map(|w| w.to_uppercase())
+        .collect();
+    let words: Vec<&str> = words.iter().map(String::as_str).collect();
+
+    let rest = match words.as_slice() {
+        ["CREATE", "OR", "REPLACE", rest @ ..] => rest,
+        ["CREATE", rest @ ..] => rest,
+        _ => return false,
+    };
+    matches!(rest, ["TEMP" | "TEMPORARY", "VIEW" | "TABLE", ..])
+}
+
+/// Get the first keyword of a statement, skipping whitespace, comments and
+/// opening parentheses.
+pub(crate) fn leading_keyword(sql: &str) -> &str {
+    let rest = skip_leading_comments(sql);
+    let end = rest
+        .find(|c: char| !c.is_ascii_alphabetic())
+        .unwrap_or(rest.len());
+    &rest[..end]
+}
+
+/// Skip leading whitespace, comments and opening parentheses.
+fn skip_leading_comments(sql: &str) -> &str {
+    let mut rest = sql;
+    loop {
+        rest = rest.trim_start_matches(|c: char| c.is_whitespace() || c == '(');
+        if let Some(after) = rest.strip_prefix("--") {
+            rest = after.split_once('\n').map(|(_, r)| r).unwrap_or("");
+        } else if let Some(after) = rest.strip_prefix("/*") {
+            rest = after.split_once("*/").map(|(_, r)| r).unwrap_or("");
+        } else {
+            break;
+        }
+    }
+    rest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_allows_read_only_statements() {
+        assert!(check_statement("SELECT * FROM benchmarks").is_ok());
+        assert!(check_statement("  with t AS (SELECT 1) SELECT * FROM t").is_ok());
+        assert!(check_statement("FROM benchmarks SELECT name").is_ok());
+        assert!(check_statement("(SELECT 1) UNION (SELECT 2)").is_ok());
+        assert!(check_statement("-- cheapest\n/* models */ SELECT 1").is_ok());
+        assert!(check_statement("SUMMARIZE benchmarks").is_ok());
+    }
+
+    #[test]
+    fn test_allows_temp_views_and_tables() {
+        assert!(check_statement("CREATE TEMP VIEW cheap AS SELECT 1").is_ok());
+        assert!(check_statement("create or replace temporary table t AS SELECT 1").is_ok());
+        assert!(check_statement("CREATE VIEW cheap AS SELECT 1").is_err());
+        assert!(check_statement("CREATE TEMP MACRO m(x) AS x").is_err());
+    }
+
+    #[test]
+    fn test_blocks_other_statements() {
+        for sql in [
+            "COPY benchmarks TO 'out.csv'",
+            "INSTALL httpfs",
+            "LOAD httpfs",
+            "ATTACH 'other.db'",
+            "CREATE TABLE t AS SELECT 1",
+            "SET enable_external_access = true",
+            "PRAGMA version",
+            "-- comment\nDROP VIEW benchmarks",
+            "",
+        ] {
+            let err = check_statement(sql).unwrap_err();
+            assert!(matches!(err, AppError::Sandbox(_)), "{sql}");
+        }
+    }
+
+    #[test]
+    fn test_lockdown_sql() {
+        let sql = lockdown_sql(&[PathBuf::from("/cache/it's/benchmarks.parquet")]);
+        assert!(sql.contains("SET allowed_paths = ['/cache/it''s/benchmarks.parquet']"));
+        assert!(sql.contains("SET enable_external_access = false"));
+        assert!(sql.contains("SET lock_configuration = true"));
+    }
+}
@@ -0,0 +1,124 @@
+//! Multi-statement SQL scripts.
+//!
+//! Scripts come from `query -f FILE`, `query -` (stdin) or a command-line
+//! query containing several `;`-separated statements.
+
+use super::params::placeholders;
+use super::sandbox::leading_keyword;
+use crate::error::{AppError, Result};
+
+/// Split a script into statements on `;`, ignoring semicolons inside string
+/// literals, quoted identifiers and comments.
+///
+/// Statements that are empty or contain only comments are dropped.
+pub fn split_statements(sql: &str) -> Vec<String> {
+    let chars: Vec<char> = sql.chars().collect();
+    let mut statements = Vec::new();
+    let mut current = String::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let end = match c {
+            '\'' | '"' => chars[i + 1..]
+                .iter()
+                .position(|&q| q == c)
+                .map(|p| i + p + 2)
+                .unwrap_or(chars.len()),
+            '-' if chars.get(i + 1) == Some(&'-') => chars[i..]
+                .iter()
+                .position(|&ch| ch == '\n')
+                .map(|p| i + p + 1)
+                .unwrap_or(chars.len()),
+            '/' if chars.get(i + 1) == Some(&'*') => (i + 2..chars.len().saturating_sub(1))
+                .find(|&j| chars[j] == '*' && chars[j + 1] == '/')
+                .map(|j| j + 2)
+                .unwrap_or(chars.len()),
+            ';' => {
+                statements.push(std::mem::take(&mut current));
+                i += 1;
+                continue;
+            }
+            _ => i + 1,
+        };
+        current.extend(&chars[i..end]);
+        i = end;
+    }
+    statements.push(current);
+
+    statements
+        .into_iter()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !leading_keyword(s).is_empty())
+        .collect()
+}
+
+/// Whether a statement produces a result worth printing.
+///
+/// `CREATE TEMP VIEW/TABLE` steps only set up later statements.
+pub fn returns_rows(statement: &str) -> bool {
+    !leading_keyword(statement).eq_ignore_ascii_case("CREATE")
+}
+
+/// Get the parameters used by one statement of a script.
+pub fn params_for(statement: &str, params: &[(String, String)]) -> Vec<(String, String)> {
+    let names = placeholders(statement);
+    params
+        .iter()
+        .filter(|(name, _)| names.contains(name))
+        .cloned()
+        .collect()
+}
+
+/// Check that every parameter is used by some statement of the script.
+pub fn check_params_used(sql: &str, params: &[(String, String)]) -> Result<()> {
+    let names = placeholders(sql);
+    match params.iter().find(|(name, _)| !names.contains(name)) {
+        Some((name, _)) => Err(AppError::Query(format!(
+            "Unknown parameter '{}': no statement uses :{}",
+            name, name
+        ))),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_statements() {
+        let script = "
+            -- cheap models; under $1
+            CREATE TEMP VIEW cheap AS SELECT * FROM benchmarks WHERE price < 1;
+            SELECT name FROM cheap WHERE name <> 'a;b';
+            /* done; */ SELECT \"odd;name\" FROM cheap;
+            ;
+            -- trailing comment
+        ";
+
+        let statements = split_statements(script);
+        assert_eq!(statements.len(), 3);
+        assert!(statements[0].ends_with("WHERE price < 1"));
+        assert_eq!(statements[1], "SELECT name FROM cheap WHERE name <> 'a;b'");
+        assert_eq!(statements[2], "/* done; */ SELECT \"odd;name\" FROM cheap");
+    }
+
+    #[test]
+    fn test_returns_rows() {
+        assert!(returns_rows("SELECT 1"));
+        assert!(returns_rows("FROM benchmarks"));
+        assert!(!returns_rows("-- setup\ncreate temp view v AS SELECT 1"));
+    }
+
+    #[test]
+    fn test_params_for() {
+        let params = vec![
+            ("a".to_string(), "1".to_string()),
+            ("b".to_string(), "2".to_string()),
+        ];
+        assert_eq!(params_for("SELECT :b", &params), vec![params[1].clone()]);
+        assert!(check_params_used("SELECT :a; SELECT :b", &params).is_ok());
+        assert!(check_params_used("SELECT :a", &params).is_err());
+    }
+}
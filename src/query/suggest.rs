@@ -0,0 +1,217 @@
+//! Did-you-mean suggestions for unknown tables and columns.
+//!
+//! DuckDB's binder and catalog errors name the identifier it could not
+//! resolve; we match it by edit distance against the tables, views and
+//! columns registered on the connection, so history and user-defined views
+//! are suggested too.
+
+use crate::error::AppError;
+use duckdb::Connection;
+
+/// Maximum number of suggestions returned.
+const MAX_SUGGESTIONS: usize = 3;
+
+/// Names of tables and views in the connection's catalog.
+const TABLE_NAMES_SQL: &str = "SELECT DISTINCT table_name FROM information_schema.tables \
+     WHERE table_schema NOT IN ('information_schema', 'pg_catalog')";
+
+/// Names of columns in the connection's catalog.
+const COLUMN_NAMES_SQL: &str = "SELECT DISTINCT column_name FROM information_schema.columns \
+     WHERE table_schema NOT IN ('information_schema', 'pg_catalog')";
+
+/// Build an `UnknownName` error from a DuckDB error message, if the message
+/// reports a missing table or column.
+pub fn unknown_name_error(conn: &Connection, msg: &str) -> Option<AppError> {
+    let (kind, name) = if let Some(name) = extract(msg, "Referenced column \"", "\"") {
+        ("column", name)
+    } else if let Some(name) = extract(msg, "Table with name ", " does not exist") {
+        ("table", name)
+    } else {
+        return None;
+    };
+
+    let candidates = match kind {
+        "column" => catalog_names(conn, COLUMN_NAMES_SQL),
+        _ => catalog_names(conn, TABLE_NAMES_SQL),
+    };
+    let suggestions = closest_names(&name, candidates.iter().map(String::as_str));
+
+    Some(AppError::UnknownName {
+        kind,
+        name,
+        suggestions,
+        details: msg.to_string(),
+    })
+}
+
+/// Run a catalog query returning one name per row.
+fn catalog_names(conn: &Connection, sql: &str) -> Vec<String> {
+    let query = || -> duckdb::Result<Vec<String>> {
+        let mut stmt = conn.prepare(sql)?;
+        let rows = stmt.query_map([], |row| row.get(0))?;
+        rows.collect()
+    };
+    query().unwrap_or_default()
+}
+
+/// Get the text between `prefix` and the following `suffix`.
+fn extract(msg: &str, prefix: &str, suffix: &str) -> Option<String> {
+    let start = msg.find(prefix)? + prefix.len();
+    let len = msg[start..].find(suffix)?;
+    Some(msg[start..start + len].to_string())
+}
+
+/// Rank candidates by similarity to `name`, returning the closest few.
+///
+/// Besides plain edit distance, candidates that contain the name
+/// (`context` -> `context_window`) or use the same words in another order
+/// (`price_input` -> `input_price`) are treated as close matches.
+pub fn closest_names<'a>(name: &str, candidates: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+    let name = name.to_lowercase();
+    let max_distance = (name.chars().count() / 3).max(2);
+
+    let mut scored: Vec<(usize, usize, &str)> = candidates
+        .into_iter()
+        .filter(|c| *c != name)
+        .filter_map(|candidate| {
+            let distance = edit_distance(&name, candidate);
+            let rank = if same_words(&name, candidate) {
+                0
+            } else if candidate.contains(name.as_str())
+                || (candidate.len() > 3 && name.contains(candidate))
+            {
+                1
+            } else if distance <= max_distance {
+                2
+            } else {
+                return None;
+            };
+            Some((rank, distance, candidate))
+        })
+        .collect();
+
+    scored.sort();
+    scored
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, _, c)| c.to_string())
+        .collect()
+}
+
+/// Whether two snake_case names use the same words in any order.
+fn same_words(a: &str, b: &str) -> bool {
+    let mut a: Vec<&str> = a.split('_').collect();
+    let mut b: Vec<&str> = b.split('_').collect();
+    a.sort_unstable();
+    b.sort_unstable();
+    a.len() > 1 && a == b
+}
+
+/// Levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut curr = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != *cb);
+            curr[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        prev = curr;
+    }
+
+    prev[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::ALL_TABLES;
+
+    /// All column names across the known tables.
+    fn known_columns() -> Vec<&'static str> {
+        ALL_TABLES
+            .iter()
+            .flat_map(|t| t.columns.iter().map(|c| c.name))
+            .collect()
+    }
+
+    /// A connection with a table and a view standing in for the cache.
+    fn catalog() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE benchmarks (name VARCHAR, input_price DOUBLE);
+             CREATE VIEW cheap_models AS SELECT name FROM benchmarks;",
+        )
+        .unwrap();
+        conn
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("price", "price"), 0);
+    }
+
+    #[test]
+    fn test_closest_names() {
+        let columns = known_columns();
+        assert_eq!(
+            closest_names("price_input", columns.iter().copied())[0],
+            "input_price"
+        );
+        assert_eq!(
+            closest_names("context", columns.iter().copied())[0],
+            "context_window"
+        );
+        assert_eq!(
+            closest_names("inteligence", columns.iter().copied())[0],
+            "intelligence"
+        );
+        assert!(closest_names("zzzzzzzz", columns.iter().copied()).is_empty());
+    }
+
+    #[test]
+    fn test_unknown_name_error() {
+        let conn = catalog();
+        let err = unknown_name_error(
+            &conn,
+            "Binder Error: Referenced column \"price_input\" not found in FROM clause!",
+        )
+        .unwrap();
+        match err {
+            AppError::UnknownName {
+                kind,
+                name,
+                suggestions,
+                ..
+            } => {
+                assert_eq!(kind, "column");
+                assert_eq!(name, "price_input");
+                assert_eq!(suggestions[0], "input_price");
+            }
+            other => panic!("unexpected error: {other}"),
+        }
+
+        let err = unknown_name_error(
+            &conn,
+            "Catalog Error: Table with name benchmark does not exist!\nDid you mean \"pg_tables\"?",
+        )
+        .unwrap();
+        assert!(err.to_string().contains("Did you mean 'benchmarks'?"));
+
+        // Views in the catalog are suggested as well as tables
+        let err = unknown_name_error(
+            &conn,
+            "Catalog Error: Table with name cheap_model does not exist!",
+        )
+        .unwrap();
+        assert!(err.to_string().contains("Did you mean 'cheap_models'?"));
+
+        assert!(
+            unknown_name_error(&conn, "Parser Error: syntax error at or near \"FORM\"").is_none()
+        );
+    }
+}
@@ -0,0 +1,443 @@
+//! Typed cell values for query results.
+//!
+//! Query results keep DuckDB's native types so that JSON and CSV output can
+//! preserve full precision, while the human-readable formats round floats to
+//! a configurable number of decimal places.
+//!
+//! Dates and timestamps are rendered as ISO-8601. Nested values (lists,
+//! structs and maps) become native JSON arrays/objects in JSON output and
+//! compact JSON text in every other format.
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, SecondsFormat, Utc};
+use duckdb::arrow::array::Array;
+use serde::{Serialize, Serializer};
+
+/// Default number of decimal places for floats in human-readable output.
+pub const DEFAULT_PRECISION: usize = 2;
+
+/// Days between 0001-01-01 (CE day 1) and 1970-01-01.
+const UNIX_EPOCH_DAYS_FROM_CE: i32 = 719_163;
+
+/// A single typed value in a query result.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CellValue {
+    Null,
+    Bool(bool),
+    Int(i64),
+    UInt(u64),
+    Float(f64),
+    Text(String),
+    Date(NaiveDate),
+    Time(NaiveTime),
+    /// Timestamp without time zone.
+    Timestamp(NaiveDateTime),
+    /// Timestamp with time zone, normalized to UTC.
+    TimestampTz(DateTime<Utc>),
+    List(Vec<CellValue>),
+    /// Struct or map entries, in order.
+    Object(Vec<(String, CellValue)>),
+}
+
+impl CellValue {
+    /// Convert an Arrow array value at a given row index to a typed cell.
+    pub fn from_arrow(array: &dyn Array, row: usize) -> Self {
+        use duckdb::arrow::array::*;
+        use duckdb::arrow::datatypes::*;
+
+        if array.is_null(row) {
+            return Self::Null;
+        }
+
+        match array.data_type() {
+            DataType::Null => Self::Null,
+            DataType::Boolean => {
+                let arr = array.as_any().downcast_ref::<BooleanArray>().unwrap();
+                Self::Bool(arr.value(row))
+            }
+            DataType::Int8 => {
+                let arr = array.as_any().downcast_ref::<Int8Array>().unwrap();
+                Self::Int(arr.value(row).into())
+            }
+            DataType::Int16 => {
+                let arr = array.as_any().downcast_ref::<Int16Array>().unwrap();
+                Self::Int(arr.value(row).into())
+            }
+            DataType::Int32 => {
+                let arr = array.as_any().downcast_ref::<Int32Array>().unwrap();
+                Self::Int(arr.value(row).into())
+            }
+            DataType::Int64 => {
+                let arr = array.as_any().downcast_ref::<Int64Array>().unwrap();
+                Self::Int(arr.value(row))
+            }
+            DataType::UInt8 => {
+                let arr = array.as_any().downcast_ref::<UInt8Array>().unwrap();
+                Self::UInt(arr.value(row).into())
+            }
+            DataType::UInt16 => {
+                let arr = array.as_any().downcast_ref::<UInt16Array>().unwrap();
+                Self::UInt(arr.value(row).into())
+            }
+            DataType::UInt32 => {
+                let arr = array.as_any().downcast_ref::<UInt32Array>().unwrap();
+                Self::UInt(arr.value(row).into())
+            }
+            DataType::UInt64 => {
+                let arr = array.as_any().downcast_ref::<UInt64Array>().unwrap();
+                Self::UInt(arr.value(row))
+            }
+            DataType::Float32 => {
+                let arr = array.as_any().downcast_ref::<Float32Array>().unwrap();
+                // Go through the shortest decimal representation so that
+                // 0.075f32 doesn't turn into 0.07500000298023224
+                let value = arr.value(row);
+                Self::Float(value.to_string().parse().unwrap_or(value as f64))
+            }
+            DataType::Float64 => {
+                let arr = array.as_any().downcast_ref::<Float64Array>().unwrap();
+                Self::Float(arr.value(row))
+            }
+            DataType::Utf8 => {
+                let arr = array.as_any().downcast_ref::<StringArray>().unwrap();
+                Self::Text(arr.value(row).to_string())
+            }
+            DataType::LargeUtf8 => {
+                let arr = array.as_any().downcast_ref::<LargeStringArray>().unwrap();
+                Self::Text(arr.value(row).to_string())
+            }
+            DataType::Binary => {
+                let arr = array.as_any().downcast_ref::<BinaryArray>().unwrap();
+                Self::Text(format!("<binary:{} bytes>", arr.value(row).len()))
+            }
+            DataType::LargeBinary => {
+                let arr = array.as_any().downcast_ref::<LargeBinaryArray>().unwrap();
+                Self::Text(format!("<binary:{} bytes>", arr.value(row).len()))
+            }
+            DataType::Date32 => {
+                let arr = array.as_any().downcast_ref::<Date32Array>().unwrap();
+                // Date32 is days since epoch
+                let days = arr.value(row);
+                days.checked_add(UNIX_EPOCH_DAYS_FROM_CE)
+                    .and_then(NaiveDate::from_num_days_from_ce_opt)
+                    .map(Self::Date)
+                    .unwrap_or(Self::Int(days.into()))
+            }
+            DataType::Date64 => {
+                let arr = array.as_any().downcast_ref::<Date64Array>().unwrap();
+                // Date64 is milliseconds since epoch
+                let ms = arr.value(row);
+                DateTime::from_timestamp_millis(ms)
+                    .map(|dt| Self::Date(dt.date_naive()))
+                    .unwrap_or(Self::Int(ms))
+            }
+            DataType::Time32(unit) => {
+                let value: i64 = match unit {
+                    TimeUnit::Second => array
+                        .as_any()
+                        .downcast_ref::<Time32SecondArray>()
+                        .unwrap()
+                        .value(row)
+                        .into(),
+                    _ => array
+                        .as_any()
+                        .downcast_ref::<Time32MillisecondArray>()
+                        .unwrap()
+                        .value(row)
+                        .into(),
+                };
+                time_from_midnight(value, unit)
+                    .map(Self::Time)
+                    .unwrap_or(Self::Int(value))
+            }
+            DataType::Time64(unit) => {
+                let value = match unit {
+                    TimeUnit::Nanosecond => array
+                        .as_any()
+                        .downcast_ref::<Time64NanosecondArray>()
+                        .unwrap()
+                        .value(row),
+                    _ => array
+                        .as_any()
+                        .downcast_ref::<Time64MicrosecondArray>()
+                        .unwrap()
+                        .value(row),
+                };
+                time_from_midnight(value, unit)
+                    .map(Self::Time)
+                    .unwrap_or(Self::Int(value))
+            }
+            DataType::Timestamp(unit, tz) => {
+                let value = match unit {
+                    TimeUnit::Second => array
+                        .as_any()
+                        .downcast_ref::<TimestampSecondArray>()
+                        .unwrap()
+                        .value(row),
+                    TimeUnit::Millisecond => array
+                        .as_any()
+                        .downcast_ref::<TimestampMillisecondArray>()
+                        .unwrap()
+                        .value(row),
+                    TimeUnit::Microsecond => array
+                        .as_any()
+                        .downcast_ref::<TimestampMicrosecondArray>()
+                        .unwrap()
+                        .value(row),
+                    TimeUnit::Nanosecond => array
+                        .as_any()
+                        .downcast_ref::<TimestampNanosecondArray>()
+                        .unwrap()
+                        .value(row),
+                };
+                // Arrow stores zoned timestamps as UTC instants
+                match timestamp_from_epoch(value, unit) {
+                    Some(dt) if tz.is_some() => Self::TimestampTz(dt),
+                    Some(dt) => Self::Timestamp(dt.naive_utc()),
+                    None => Self::Int(value),
+                }
+            }
+            DataType::Decimal128(_, scale) => {
+                let arr = array.as_any().downcast_ref::<Decimal128Array>().unwrap();
+                let value = arr.value(row);
+                if *scale <= 0 {
+                    i64::try_from(value)
+                        .map(Self::Int)
+                        .unwrap_or(Self::Float(value as f64))
+                } else {
+                    // Format then parse to avoid binary rounding of the division
+                    let divisor = 10i128.pow(*scale as u32);
+                    let int_part = value / divisor;
+                    let frac_part = (value % divisor).abs();
+                    let sign = if value < 0 && int_part == 0 { "-" } else { "" };
+                    let text = format!(
+                        "{}{}.{:0>width$}",
+                        sign,
+                        int_part,
+                        frac_part,
+                        width = *scale as usize
+                    );
+                    Self::Float(text.parse().unwrap_or(value as f64))
+                }
+            }
+            DataType::List(_) => {
+                let arr = array.as_any().downcast_ref::<ListArray>().unwrap();
+                Self::from_arrow_list(arr.value(row).as_ref())
+            }
+            DataType::LargeList(_) => {
+                let arr = array.as_any().downcast_ref::<LargeListArray>().unwrap();
+                Self::from_arrow_list(arr.value(row).as_ref())
+            }
+            DataType::FixedSizeList(_, _) => {
+                let arr = array.as_any().downcast_ref::<FixedSizeListArray>().unwrap();
+                Self::from_arrow_list(arr.value(row).as_ref())
+            }
+            DataType::Struct(_) => {
+                let arr = array.as_any().downcast_ref::<StructArray>().unwrap();
+                Self::Object(
+                    arr.column_names()
+                        .into_iter()
+                        .zip(arr.columns())
+                        .map(|(name, col)| (name.to_string(), Self::from_arrow(col.as_ref(), row)))
+                        .collect(),
+                )
+            }
+            DataType::Map(_, _) => {
+                let arr = array.as_any().downcast_ref::<MapArray>().unwrap();
+                let entries = arr.value(row);
+                let (keys, values) = (entries.column(0), entries.column(1));
+                Self::Object(
+                    (0..entries.len())
+                        .map(|i| {
+                            (
+                                Self::from_arrow(keys.as_ref(), i).to_text(),
+                                Self::from_arrow(values.as_ref(), i),
+                            )
+                        })
+                        .collect(),
+                )
+            }
+            // For other types (intervals, durations, ...), use Arrow's display formatting
+            _ => {
+                use duckdb::arrow::util::display::{ArrayFormatter, FormatOptions};
+                let options = FormatOptions::default();
+                match ArrayFormatter::try_new(array, &options) {
+                    Ok(formatter) => Self::Text(formatter.value(row).to_string()),
+                    Err(_) => Self::Text(format!("<{}>", array.data_type())),
+                }
+            }
+        }
+    }
+
+    /// Convert every element of a list's child array.
+    fn from_arrow_list(values: &dyn Array) -> Self {
+        Self::List(
+            (0..values.len())
+                .map(|i| Self::from_arrow(values, i))
+                .collect(),
+        )
+    }
+
+    /// Check if the value is NULL.
+    pub fn is_null(&self) -> bool {
+        matches!(self, Self::Null)
+    }
+
+    /// Render for human-readable output (markdown, table, plain).
+    ///
+    /// Floats are rounded to `precision` decimal places and NULL is empty.
+    pub fn display(&self, precision: usize) -> String {
+        match self {
+            Self::Float(v) => format!("{:.*}", precision, v),
+            other => other.to_text(),
+        }
+    }
+
+    /// Render at full precision (CSV and other machine-readable text).
+    ///
+    /// NULL is empty, temporal values are ISO-8601 and nested values are JSON.
+    pub fn to_text(&self) -> String {
+        match self {
+            Self::Null => String::new(),
+            Self::Bool(v) => v.to_string(),
+            Self::Int(v) => v.to_string(),
+            Self::UInt(v) => v.to_string(),
+            Self::Float(v) => v.to_string(),
+            Self::Text(v) => v.clone(),
+            Self::Date(v) => v.to_string(),
+            Self::Time(v) => v.format("%H:%M:%S%.f").to_string(),
+            Self::Timestamp(v) => v.format("%Y-%m-%dT%H:%M:%S%.f").to_string(),
+            Self::TimestampTz(v) => v.to_rfc3339_opts(SecondsFormat::AutoSi, true),
+            Self::List(_) | Self::Object(_) => serde_json::to_string(self).unwrap_or_default(),
+        }
+    }
+}
+
+/// Convert an epoch offset in the given unit to a UTC timestamp.
+fn timestamp_from_epoch(
+    value: i64,
+    unit: &duckdb::arrow::datatypes::TimeUnit,
+) -> Option<DateTime<Utc>> {
+    use duckdb::arrow::datatypes::TimeUnit;
+
+    match unit {
+        TimeUnit::Second => DateTime::from_timestamp(value, 0),
+        TimeUnit::Millisecond => DateTime::from_timestamp_millis(value),
+        TimeUnit::Microsecond => DateTime::from_timestamp_micros(value),
+        TimeUnit::Nanosecond => Some(DateTime::from_timestamp_nanos(value)),
+    }
+}
+
+/// Convert an offset since midnight in the given unit to a time of day.
+fn time_from_midnight(value: i64, unit: &duckdb::arrow::datatypes::TimeUnit) -> Option<NaiveTime> {
+    use duckdb::arrow::datatypes::TimeUnit;
+
+    let nanos = match unit {
+        TimeUnit::Second => value.checked_mul(1_000_000_000)?,
+        TimeUnit::Millisecond => value.checked_mul(1_000_000)?,
+        TimeUnit::Microsecond => value.checked_mul(1_000)?,
+        TimeUnit::Nanosecond => value,
+    };
+    let secs = u32::try_from(nanos / 1_000_000_000).ok()?;
+    let frac = u32::try_from(nanos % 1_000_000_000).ok()?;
+    NaiveTime::from_num_seconds_from_midnight_opt(secs, frac)
+}
+
+impl Serialize for CellValue {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Null => serializer.serialize_none(),
+            Self::Bool(v) => serializer.serialize_bool(*v),
+            Self::Int(v) => serializer.serialize_i64(*v),
+            Self::UInt(v) => serializer.serialize_u64(*v),
+            // Non-finite floats have no JSON representation
+            Self::Float(v) if !v.is_finite() => serializer.serialize_none(),
+            Self::Float(v) => serializer.serialize_f64(*v),
+            Self::Text(v) => serializer.serialize_str(v),
+            Self::Date(_) | Self::Time(_) | Self::Timestamp(_) | Self::TimestampTz(_) => {
+                serializer.serialize_str(&self.to_text())
+            }
+            Self::List(values) => serializer.collect_seq(values),
+            Self::Object(entries) => {
+                serializer.collect_map(entries.iter().map(|(k, v)| (k.as_str(), v)))
+            }
+        }
+    }
+}
+
+impl From<&str> for CellValue {
+    fn from(value: &str) -> Self {
+        Self::Text(value.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_rounds_floats() {
+        let value = CellValue::Float(0.075);
+        assert_eq!(value.display(2), "0.07");
+        assert_eq!(value.display(4), "0.0750");
+        assert_eq!(value.to_text(), "0.075");
+    }
+
+    #[test]
+    fn test_null_rendering() {
+        assert_eq!(CellValue::Null.display(2), "");
+        assert_eq!(CellValue::Null.to_text(), "");
+        assert_eq!(serde_json::to_string(&CellValue::Null).unwrap(), "null");
+    }
+
+    #[test]
+    fn test_json_serialization() {
+        let values = vec![
+            CellValue::Bool(true),
+            CellValue::Int(42),
+            CellValue::Float(0.075),
+            CellValue::Text("gpt-4o".into()),
+            CellValue::Date(NaiveDate::from_ymd_opt(2024, 5, 13).unwrap()),
+            CellValue::List(vec![CellValue::Int(1), CellValue::Null]),
+            CellValue::Object(vec![("id".into(), CellValue::Text("a".into()))]),
+        ];
+        assert_eq!(
+            serde_json::to_string(&values).unwrap(),
+            r#"[true,42,0.075,"gpt-4o","2024-05-13",[1,null],{"id":"a"}]"#
+        );
+    }
+
+    #[test]
+    fn test_temporal_rendering() {
+        let date = NaiveDate::from_ymd_opt(2024, 5, 13).unwrap();
+        let ts = date.and_hms_milli_opt(9, 30, 0, 250).unwrap();
+
+        assert_eq!(
+            CellValue::Timestamp(ts).to_text(),
+            "2024-05-13T09:30:00.250"
+        );
+        assert_eq!(
+            CellValue::TimestampTz(ts.and_utc()).to_text(),
+            "2024-05-13T09:30:00.250Z"
+        );
+        assert_eq!(
+            CellValue::Time(NaiveTime::from_hms_opt(9, 30, 0).unwrap()).to_text(),
+            "09:30:00"
+        );
+        assert_eq!(
+            serde_json::to_string(&CellValue::Timestamp(ts)).unwrap(),
+            r#""2024-05-13T09:30:00.250""#
+        );
+    }
+
+    #[test]
+    fn test_nested_values_render_as_json_text() {
+        let value = CellValue::Object(vec![
+            ("name".into(), CellValue::Text("gpt-4o".into())),
+            (
+                "tags".into(),
+                CellValue::List(vec![CellValue::Float(1.5), CellValue::Bool(false)]),
+            ),
+        ]);
+        assert_eq!(value.display(2), r#"{"name":"gpt-4o","tags":[1.5,false]}"#);
+    }
+}
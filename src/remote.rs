@@ -2,13 +2,27 @@
 //!
 //! This module provides functionality to fetch pre-built Parquet data files
 //! from GitHub Releases, eliminating the need for users to have API keys.
+//! The release can also be served from a mirror: any `http(s)://` URL or a
+//! `file://` directory holding the same `manifest.json` and Parquet files.
 
+use crate::config::{Config, DataConfig};
 use crate::error::{AppError, Result};
-use chrono::{DateTime, Utc};
+use crate::offline;
+use crate::progress::{FileProgress, Progress};
+use crate::signature::{SignaturePolicy, SIGNATURE_FILE};
+use chrono::{DateTime, NaiveDate, Utc};
+use futures_util::{stream, StreamExt};
+use reqwest::header::{CONTENT_RANGE, ETAG, IF_NONE_MATCH, IF_RANGE, RANGE};
+use reqwest::{StatusCode, Url};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
+use std::collections::hash_map::RandomState;
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::hash::{BuildHasher, Hasher};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
 
 /// GitHub repository for data releases.
 const GITHUB_REPO: &str = "richard-gyiko/which-llm";
@@ -16,6 +30,33 @@ const GITHUB_REPO: &str = "richard-gyiko/which-llm";
 /// Release tag for latest data.
 const DATA_RELEASE_TAG: &str = "data/latest";
 
+/// First dated data release published with a manifest signature. Earlier
+/// releases are unsigned and stay usable when pinned.
+const FIRST_SIGNED_RELEASE: &str = "data/2026-10-17";
+
+/// Environment variable overriding the data base URL.
+pub const DATA_URL_ENV: &str = "WHICH_LLM_DATA_URL";
+
+/// Manifest file name, in the release and in the cache.
+const MANIFEST_FILE: &str = "manifest.json";
+
+/// Data files in a release, without the `.parquet` extension.
+const DATA_FILES: &[&str] = &[
+    "benchmarks",
+    "models",
+    "text_to_image",
+    "image_editing",
+    "text_to_speech",
+    "text_to_video",
+    "image_to_video",
+];
+
+/// Longest delay between retries.
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Maximum number of files downloaded at once.
+const MAX_CONCURRENT_DOWNLOADS: usize = 4;
+
 /// TTL for remote data cache (24 hours).
 const REMOTE_DATA_TTL_HOURS: i64 = 24;
 
@@ -55,101 +96,546 @@ pub struct Attribution {
     pub url: String,
 }
 
-/// Client for fetching data from GitHub Releases.
+/// Timeouts and retries for HTTP downloads.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FetchOptions {
+    /// Time to wait for a connection.
+    pub connect_timeout: Duration,
+    /// Time to wait for more data before giving up on a request.
+    pub read_timeout: Duration,
+    /// Times a failed request is retried.
+    pub retries: u32,
+    /// Delay before the first retry, doubled for each later one.
+    pub backoff: Duration,
+}
+
+impl Default for FetchOptions {
+    fn default() -> Self {
+        Self {
+            connect_timeout: Duration::from_secs(10),
+            read_timeout: Duration::from_secs(30),
+            retries: 3,
+            backoff: Duration::from_millis(500),
+        }
+    }
+}
+
+impl FetchOptions {
+    /// Options from `[data]` in config.toml, with defaults for unset keys.
+    pub fn from_config(data: &DataConfig) -> Self {
+        let default = Self::default();
+        Self {
+            connect_timeout: data
+                .connect_timeout
+                .map_or(default.connect_timeout, Duration::from_secs),
+            read_timeout: data
+                .read_timeout
+                .map_or(default.read_timeout, Duration::from_secs),
+            retries: data.retries.unwrap_or(default.retries),
+            backoff: default.backoff,
+        }
+    }
+
+    /// Delay before retry number `attempt` (from 0): exponential backoff,
+    /// capped at [`MAX_BACKOFF`], with the upper half randomized.
+    fn retry_delay(&self, attempt: u32) -> Duration {
+        let delay = self
+            .backoff
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(MAX_BACKOFF);
+        delay / 2 + jitter(delay / 2)
+    }
+}
+
+/// Get a random duration up to `max`.
+fn jitter(max: Duration) -> Duration {
+    // RandomState is randomly seeded, so no RNG crate is needed
+    let random = RandomState::new().build_hasher().finish();
+    max.mul_f64(random as f64 / u64::MAX as f64)
+}
+
+/// Where hosted data is fetched from, and how.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataSource {
+    /// Base URL holding `manifest.json` and the Parquet files.
+    pub base_url: String,
+    /// GitHub release tag, or `None` for a mirror.
+    pub tag: Option<String>,
+    /// Timeouts and retries for HTTP downloads.
+    pub fetch: FetchOptions,
+    /// Which manifest signatures are trusted.
+    pub signatures: SignaturePolicy,
+    /// Never use the network; use cached data even when stale.
+    pub offline: bool,
+}
+
+impl DataSource {
+    /// The GitHub release with the given tag.
+    pub fn github(tag: &str) -> Self {
+        Self {
+            base_url: format!(
+                "https://github.com/{}/releases/download/{}",
+                GITHUB_REPO, tag
+            ),
+            tag: Some(tag.to_string()),
+            fetch: FetchOptions::default(),
+            signatures: SignaturePolicy {
+                release: is_signed_release(tag),
+                ..SignaturePolicy::default()
+            },
+            offline: false,
+        }
+    }
+
+    /// A mirror at `base_url`.
+    pub fn mirror(base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into(),
+            tag: None,
+            fetch: FetchOptions::default(),
+            signatures: SignaturePolicy::default(),
+            offline: false,
+        }
+    }
+
+    /// Resolve the data source.
+    ///
+    /// A mirror URL comes from `WHICH_LLM_DATA_URL`, then the profile's
+    /// `data_url`, then `url` under `[data]` in config.toml. Without one, the
+    /// GitHub release tagged `tag` (or `tag` under `[data]`, default
+    /// `data/latest`) is used. Timeouts, retries and trusted signing keys come
+    /// from `[data]`.
+    pub fn resolve(config: &Config, profile: Option<&str>, tag: Option<&str>) -> Result<Self> {
+        let mirror = std::env::var(DATA_URL_ENV)
+            .ok()
+            .filter(|url| !url.is_empty())
+            .or_else(|| config.data_url(profile).map(String::from));
+        let mut source = Self::from_parts(mirror, tag.or(config.data.tag.as_deref()))?;
+        source.fetch = FetchOptions::from_config(&config.data);
+        source.signatures = SignaturePolicy {
+            release: source.signatures.release,
+            ..SignaturePolicy::from_config(&config.data)
+        };
+        Ok(source)
+    }
+
+    fn from_parts(mirror: Option<String>, tag: Option<&str>) -> Result<Self> {
+        match (mirror, tag) {
+            (Some(url), None) => Ok(Self::mirror(url)),
+            (Some(url), Some(tag)) => Err(AppError::Config(format!(
+                "Data tag '{}' selects a GitHub release; it can't be used with data URL '{}'.",
+                tag, url
+            ))),
+            (None, tag) => Ok(Self::github(tag.unwrap_or(DATA_RELEASE_TAG))),
+        }
+    }
+
+    /// Check if the source is a pinned (immutable) release.
+    fn is_pinned(&self) -> bool {
+        matches!(self.tag.as_deref(), Some(tag) if tag != DATA_RELEASE_TAG)
+    }
+}
+
+/// Check if a GitHub release is signed: `data/latest` and dated releases
+/// since [`FIRST_SIGNED_RELEASE`] are.
+fn is_signed_release(tag: &str) -> bool {
+    let dated = tag
+        .strip_prefix("data/")
+        .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok());
+    // Dated tags compare as text, like their dates
+    dated.is_none() || tag >= FIRST_SIGNED_RELEASE
+}
+
+/// Parse a data base URL, which must be `http://`, `https://` or `file://`.
+fn parse_base_url(base_url: &str) -> Result<Url> {
+    // A trailing slash makes joined file names land inside the directory
+    let url = Url::parse(&format!("{}/", base_url.trim_end_matches('/')))
+        .map_err(|e| AppError::Config(format!("Invalid data URL '{}': {}", base_url, e)))?;
+    match url.scheme() {
+        "http" | "https" | "file" => Ok(url),
+        scheme => Err(AppError::Config(format!(
+            "Unsupported data URL scheme '{}' in '{}'. Use http://, https:// or file://.",
+            scheme, base_url
+        ))),
+    }
+}
+
+/// Client for fetching data from GitHub Releases or a mirror.
 pub struct RemoteDataClient {
     http_client: reqwest::Client,
     cache_dir: PathBuf,
+    source: DataSource,
+    base_url: Url,
+    /// Manifest fetched by this client, so it is fetched at most once.
+    manifest: Mutex<Option<DataManifest>>,
 }
 
 impl RemoteDataClient {
-    /// Create a new remote data client.
-    pub fn new(cache_dir: PathBuf) -> Result<Self> {
+    /// Create a new remote data client fetching from `source`.
+    pub fn with_source(cache_dir: PathBuf, source: DataSource) -> Result<Self> {
+        let base_url = parse_base_url(&source.base_url)?;
         let http_client = reqwest::Client::builder()
             .user_agent(format!("which-llm/{}", env!("CARGO_PKG_VERSION")))
+            .connect_timeout(source.fetch.connect_timeout)
+            .read_timeout(source.fetch.read_timeout)
             .build()
             .map_err(|e| AppError::Network(e.to_string()))?;
 
         Ok(Self {
             http_client,
             cache_dir,
+            source,
+            base_url,
+            manifest: Mutex::new(None),
         })
     }
 
-    /// Check if local data is fresh (within TTL).
+    /// Get the data source.
+    pub fn source(&self) -> &DataSource {
+        &self.source
+    }
+
+    /// Check if the cached manifest's signature was verified.
+    pub fn is_manifest_signed(&self) -> bool {
+        self.read_meta().is_some_and(|meta| meta.manifest_signed)
+    }
+
+    /// Get the release tag the cached data was fetched from, if recorded.
+    pub fn fetched_tag(&self) -> Option<String> {
+        self.read_meta()?.tag
+    }
+
+    /// Check that the cached data was fetched from the source's release, for
+    /// commands that read the cache without fetching.
+    pub fn ensure_cached_release(&self) -> Result<()> {
+        let Some(tag) = self.source.tag.as_deref() else {
+            return Ok(());
+        };
+        match self.fetched_tag() {
+            Some(fetched) if fetched == tag => Ok(()),
+            fetched => Err(AppError::Cache(format!(
+                "the cached data is from {}, not {}. Run 'which-llm --data-tag {} refresh' first.",
+                fetched.as_deref().unwrap_or("another source"),
+                tag,
+                tag
+            ))),
+        }
+    }
+
+    /// Check if local data is fresh: fetched from the same release and,
+    /// unless the release is pinned, within the TTL.
     pub fn is_data_fresh(&self) -> bool {
-        let meta_path = self.cache_dir.join("remote_meta.json");
-        if !meta_path.exists() {
+        let Some(meta) = self.read_meta() else {
             return false;
+        };
+        if meta.tag != self.source.tag {
+            return false;
+        }
+        if self.source.is_pinned() {
+            return true;
         }
+        meta.age()
+            .is_some_and(|age| age.num_hours() < REMOTE_DATA_TTL_HOURS)
+    }
 
-        if let Ok(content) = std::fs::read_to_string(&meta_path) {
-            if let Ok(meta) = serde_json::from_str::<RemoteMeta>(&content) {
-                if let Ok(fetched_at) = DateTime::parse_from_rfc3339(&meta.fetched_at) {
-                    let age = Utc::now().signed_duration_since(fetched_at.with_timezone(&Utc));
-                    return age.num_hours() < REMOTE_DATA_TTL_HOURS;
-                }
+    /// Warn that offline mode is using cached data that isn't fresh.
+    fn warn_stale(&self) {
+        let meta = self.read_meta();
+        match meta.as_ref().and_then(RemoteMeta::age) {
+            Some(age) if age.num_hours() >= 48 => eprintln!(
+                "Warning: offline mode, using cached data from {} days ago.",
+                age.num_days()
+            ),
+            Some(age) => eprintln!(
+                "Warning: offline mode, using cached data from {} hours ago.",
+                age.num_hours()
+            ),
+            None => eprintln!("Warning: offline mode, using cached data of unknown age."),
+        }
+
+        let cached_tag = meta.and_then(|m| m.tag);
+        if let (Some(wanted), Some(cached)) = (&self.source.tag, &cached_tag) {
+            if wanted != cached {
+                eprintln!(
+                    "Warning: the cached data is from release {}, not {}.",
+                    cached, wanted
+                );
             }
         }
-        false
     }
 
-    /// Fetch the manifest from GitHub Releases.
+    /// Read the remote metadata file.
+    fn read_meta(&self) -> Option<RemoteMeta> {
+        let content = std::fs::read_to_string(self.cache_dir.join("remote_meta.json")).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    /// Fetch the manifest.
+    ///
+    /// The manifest is fetched once per client. Over HTTP the cached copy is
+    /// revalidated with its ETag, so an unchanged manifest isn't downloaded.
     pub async fn fetch_manifest(&self) -> Result<DataManifest> {
-        let url = format!(
-            "https://github.com/{}/releases/download/{}/manifest.json",
-            GITHUB_REPO, DATA_RELEASE_TAG
-        );
+        if let Some(manifest) = self.lock_manifest().clone() {
+            return Ok(manifest);
+        }
 
-        let response = self
-            .http_client
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| AppError::Network(format!("Failed to fetch manifest: {}", e)))?;
+        let manifest_path = self.cache_dir.join(MANIFEST_FILE);
+        let signature_path = self.cache_dir.join(SIGNATURE_FILE);
+        let local = std::fs::read(&manifest_path).ok();
+        let etag = self
+            .read_meta()
+            .filter(|meta| meta.manifest_url.as_deref() == Some(self.source.base_url.as_str()))
+            .and_then(|meta| meta.manifest_etag);
 
-        if !response.status().is_success() {
-            return Err(AppError::Network(format!(
-                "Failed to fetch manifest: HTTP {}",
-                response.status()
-            )));
+        let mut progress = FileProgress::hidden(MANIFEST_FILE);
+        let fetched = match (local, etag) {
+            (Some(local), Some(etag)) => match self
+                .fetch_file_if_changed(MANIFEST_FILE, &etag, &mut progress)
+                .await?
+            {
+                Some(fetched) => fetched,
+                None => {
+                    // Unchanged on the server; the policy may have changed since
+                    let signature = std::fs::read(&signature_path).ok();
+                    self.source.signatures.verify(
+                        &local,
+                        signature.as_deref(),
+                        &self.source.base_url,
+                    )?;
+                    let manifest = parse_manifest(&local)?;
+                    *self.lock_manifest() = Some(manifest.clone());
+                    return Ok(manifest);
+                }
+            },
+            _ => {
+                self.fetch_file(MANIFEST_FILE, Partial::default(), &mut progress)
+                    .await?
+            }
+        };
+
+        let signature = self.fetch_signature().await?;
+        let signed = self.source.signatures.verify(
+            &fetched.bytes,
+            signature.as_deref(),
+            &self.source.base_url,
+        )?;
+        let manifest = parse_manifest(&fetched.bytes)?;
+
+        // Keep the bytes as signed, so the cached copy can be checked again
+        std::fs::create_dir_all(&self.cache_dir)?;
+        std::fs::write(&manifest_path, &fetched.bytes)?;
+        match &signature {
+            Some(signature) => std::fs::write(&signature_path, signature)?,
+            None if signature_path.exists() => std::fs::remove_file(&signature_path)?,
+            None => {}
         }
+        self.record_manifest(fetched.etag, signed)?;
+
+        *self.lock_manifest() = Some(manifest.clone());
+        Ok(manifest)
+    }
 
-        let manifest: DataManifest = response
-            .json()
+    /// Fetch the manifest's signature, if the policy checks signatures and
+    /// the release has one.
+    async fn fetch_signature(&self) -> Result<Option<Vec<u8>>> {
+        if !self.source.signatures.is_enabled() {
+            return Ok(None);
+        }
+        let mut progress = FileProgress::hidden(SIGNATURE_FILE);
+        match self
+            .fetch_file(SIGNATURE_FILE, Partial::default(), &mut progress)
             .await
-            .map_err(|e| AppError::Network(format!("Failed to parse manifest: {}", e)))?;
+        {
+            Ok(fetched) => Ok(Some(fetched.bytes)),
+            Err(AppError::NotFound(_)) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
 
-        Ok(manifest)
+    /// Lock the memoized manifest, ignoring poisoning.
+    fn lock_manifest(&self) -> std::sync::MutexGuard<'_, Option<DataManifest>> {
+        self.manifest.lock().unwrap_or_else(|e| e.into_inner())
     }
 
-    /// Fetch a parquet file from GitHub Releases.
+    /// Fetch a parquet file.
     pub async fn fetch_parquet(&self, filename: &str) -> Result<Vec<u8>> {
-        let url = format!(
-            "https://github.com/{}/releases/download/{}/{}",
-            GITHUB_REPO, DATA_RELEASE_TAG, filename
-        );
+        let mut progress = FileProgress::hidden(filename);
+        let fetched = self
+            .fetch_file(filename, Partial::default(), &mut progress)
+            .await?;
+        Ok(fetched.bytes)
+    }
 
-        let response = self
-            .http_client
-            .get(&url)
-            .send()
+    /// Fetch a file from the base URL, resuming from the bytes in `partial`.
+    async fn fetch_file(
+        &self,
+        filename: &str,
+        partial: Partial,
+        progress: &mut FileProgress,
+    ) -> Result<Fetched> {
+        // Only conditional requests accept a 304, so there is always a file
+        self.fetch_file_with(filename, None, partial, progress)
+            .await?
+            .ok_or_else(|| AppError::Network(format!("Failed to fetch {}: HTTP 304", filename)))
+    }
+
+    /// Fetch a file from the base URL unless it still has the given `etag`.
+    ///
+    /// Returns `None` if the server reports the file unchanged.
+    async fn fetch_file_if_changed(
+        &self,
+        filename: &str,
+        etag: &str,
+        progress: &mut FileProgress,
+    ) -> Result<Option<Fetched>> {
+        self.fetch_file_with(filename, Some(etag), Partial::default(), progress)
             .await
+    }
+
+    /// Fetch a file from the base URL, reading `file://` URLs from disk.
+    ///
+    /// With an `etag`, returns `None` if the server reports the file unchanged.
+    /// Received bytes are added to `partial` and reported to `progress`.
+    async fn fetch_file_with(
+        &self,
+        filename: &str,
+        etag: Option<&str>,
+        mut partial: Partial,
+        progress: &mut FileProgress,
+    ) -> Result<Option<Fetched>> {
+        let url = self
+            .base_url
+            .join(filename)
             .map_err(|e| AppError::Network(format!("Failed to fetch {}: {}", filename, e)))?;
 
-        if !response.status().is_success() {
-            return Err(AppError::Network(format!(
-                "Failed to fetch {}: HTTP {}",
-                filename,
-                response.status()
-            )));
+        if url.scheme() == "file" {
+            let path = url.to_file_path().map_err(|_| {
+                AppError::Config(format!("Invalid file URL '{}'", self.source.base_url))
+            })?;
+            let bytes = std::fs::read(&path).map_err(|e| match e.kind() {
+                std::io::ErrorKind::NotFound => {
+                    AppError::NotFound(format!("{} at {}", filename, path.display()))
+                }
+                _ => AppError::Network(format!(
+                    "Failed to fetch {}: {}: {}",
+                    filename,
+                    path.display(),
+                    e
+                )),
+            })?;
+            progress.advance(bytes.len() as u64);
+            partial.restart(None)?;
+            partial.append(&bytes)?;
+            return Ok(Some(partial.finish()));
+        }
+
+        offline::ensure_online(
+            self.source.offline,
+            &format!("fetch {} from {}", filename, self.source.base_url),
+        )?;
+
+        // Bytes kept from an earlier run are resumed like those of a retry
+        progress.advance(partial.bytes.len() as u64);
+        let mut attempt = 0;
+        loop {
+            match self
+                .fetch_attempt(&url, filename, etag, &mut partial, progress)
+                .await
+            {
+                Ok(fetched) => return Ok(fetched),
+                Err(Attempt::Retry(_)) if attempt < self.source.fetch.retries => {
+                    tokio::time::sleep(self.source.fetch.retry_delay(attempt)).await;
+                    attempt += 1;
+                }
+                Err(Attempt::Retry(e)) if attempt > 0 => {
+                    return Err(AppError::Network(format!(
+                        "{} (after {} attempts)",
+                        e,
+                        attempt + 1
+                    )))
+                }
+                Err(Attempt::Retry(e)) => return Err(AppError::Network(e)),
+                Err(Attempt::Fail(e)) => return Err(e),
+            }
+        }
+    }
+
+    /// Make one HTTP request for a file, appending to `partial`.
+    ///
+    /// If `partial` already holds bytes of a file with an ETag, only the rest
+    /// is requested with a `Range` header. `If-Range` makes the server send
+    /// the whole file instead if it changed since.
+    async fn fetch_attempt(
+        &self,
+        url: &Url,
+        filename: &str,
+        etag: Option<&str>,
+        partial: &mut Partial,
+        progress: &mut FileProgress,
+    ) -> std::result::Result<Option<Fetched>, Attempt> {
+        let mut request = self.http_client.get(url.clone());
+        if let Some(etag) = etag {
+            request = request.header(IF_NONE_MATCH, etag);
+        }
+        // Without an ETag there's no way to tell if the file changed since
+        let offset = partial.etag.as_ref().map_or(0, |_| partial.bytes.len());
+        if let (true, Some(validator)) = (offset > 0, &partial.etag) {
+            request = request
+                .header(RANGE, format!("bytes={}-", offset))
+                .header(IF_RANGE, validator);
+        }
+
+        let mut response = request
+            .send()
+            .await
+            .map_err(|e| Attempt::Retry(format!("Failed to fetch {}: {}", filename, e)))?;
+
+        let status = response.status();
+        if etag.is_some() && status == StatusCode::NOT_MODIFIED {
+            return Ok(None);
+        }
+        if status == StatusCode::PARTIAL_CONTENT && offset > 0 {
+            if content_range_start(&response) != Some(offset as u64) {
+                // Start over rather than splice mismatched ranges
+                return Err(abandon_resume(
+                    filename,
+                    "unexpected Content-Range",
+                    partial,
+                    progress,
+                ));
+            }
+        } else if status.is_success() {
+            let etag = response
+                .headers()
+                .get(ETAG)
+                .and_then(|value| value.to_str().ok())
+                .map(String::from);
+            partial.restart(etag).map_err(Attempt::Fail)?;
+            progress.restart();
+        } else if status == StatusCode::RANGE_NOT_SATISFIABLE && offset > 0 {
+            // The saved bytes are already the whole file, or more
+            return Err(abandon_resume(filename, "HTTP 416", partial, progress));
+        } else {
+            let message = format!("Failed to fetch {}: HTTP {}", filename, status);
+            return Err(if is_retryable(status) {
+                Attempt::Retry(message)
+            } else if status == StatusCode::NOT_FOUND {
+                Attempt::Fail(AppError::NotFound(format!("{} at {}", filename, url)))
+            } else {
+                Attempt::Fail(AppError::Network(message))
+            });
         }
 
-        let bytes = response
-            .bytes()
+        while let Some(chunk) = response
+            .chunk()
             .await
-            .map_err(|e| AppError::Network(format!("Failed to read {}: {}", filename, e)))?;
+            .map_err(|e| Attempt::Retry(format!("Failed to read {}: {}", filename, e)))?
+        {
+            partial.append(&chunk).map_err(Attempt::Fail)?;
+            progress.advance(chunk.len() as u64);
+        }
 
-        Ok(bytes.to_vec())
+        Ok(Some(partial.finish()))
     }
 
     /// Fetch and cache a parquet file if needed, with SHA256 verification.
@@ -158,23 +644,85 @@ impl RemoteDataClient {
         let local_path = self.cache_dir.join(&filename);
 
         // Check if we need to fetch
-        if !force_refresh && local_path.exists() && self.is_data_fresh() {
-            return Ok(local_path);
+        if !force_refresh && local_path.exists() {
+            if self.is_data_fresh() {
+                return Ok(local_path);
+            }
+            if self.source.offline {
+                self.warn_stale();
+                return Ok(local_path);
+            }
+        }
+
+        // Offline, missing files can only come from a file:// mirror
+        if self.source.offline && !force_refresh && self.base_url.scheme() != "file" {
+            return Err(AppError::Offline(format!(
+                "{} is not cached (expected at {}). Run 'which-llm refresh' with network \
+                 access first.",
+                filename,
+                local_path.display()
+            )));
         }
 
-        // Fetch manifest to get expected checksum
         let manifest = self.fetch_manifest().await?;
-        let expected_hash = manifest.files.get(&filename).map(|f| f.sha256.clone());
+        self.sync_parquet(&manifest, &filename, &Progress::Hidden)
+            .await?;
+
+        // Update metadata
+        self.update_meta()?;
+
+        Ok(local_path)
+    }
+
+    /// Download a parquet file unless the cached copy matches the manifest's
+    /// checksum, verifying the download against it.
+    async fn sync_parquet(
+        &self,
+        manifest: &DataManifest,
+        filename: &str,
+        progress: &Progress,
+    ) -> Result<()> {
+        let local_path = self.cache_dir.join(filename);
+        let entry = manifest.files.get(filename);
+        let expected_hash = entry.map(|f| f.sha256.clone());
+
+        // Only download files whose checksum changed
+        if expected_hash.is_some() && file_sha256(&local_path) == expected_hash {
+            progress.unchanged(filename);
+            return Ok(());
+        }
 
-        // Fetch the file
-        let data = self.fetch_parquet(&filename).await?;
+        let mut file_progress = progress.download(filename, entry.map_or(0, |f| f.size));
+        let result = self
+            .download_parquet(filename, expected_hash.as_deref(), &mut file_progress)
+            .await;
+
+        match &result {
+            Ok(()) => file_progress.done(),
+            Err(e) => file_progress.fail(&e.to_string()),
+        }
+        result
+    }
+
+    /// Download a parquet file through `<file>.part`, moving it into place
+    /// once it matches `expected_hash`.
+    async fn download_parquet(
+        &self,
+        filename: &str,
+        expected_hash: Option<&str>,
+        progress: &mut FileProgress,
+    ) -> Result<()> {
+        std::fs::create_dir_all(&self.cache_dir)?;
+        let part_path = self.cache_dir.join(format!("{}.part", filename));
+        let partial = Partial::open(part_path.clone())?;
+        let data = self.fetch_file(filename, partial, progress).await?.bytes;
 
         // Verify SHA256 checksum if available
         if let Some(expected) = expected_hash {
-            let mut hasher = Sha256::new();
-            hasher.update(&data);
-            let actual = format!("{:x}", hasher.finalize());
+            let actual = sha256_hex(&data);
             if actual != expected {
+                // Don't resume from bytes that are known to be wrong
+                remove_part(&part_path)?;
                 return Err(AppError::Cache(format!(
                     "SHA256 mismatch for {}: expected {}, got {}",
                     filename, expected, actual
@@ -182,62 +730,60 @@ impl RemoteDataClient {
             }
         }
 
-        // Write to cache
-        std::fs::create_dir_all(&self.cache_dir)?;
-        std::fs::write(&local_path, data)?;
-
-        // Update metadata
-        self.update_meta()?;
-
-        Ok(local_path)
+        // The rename consumed the `.part` file; only its ETag is left over
+        std::fs::rename(&part_path, self.cache_dir.join(filename))?;
+        remove_if_exists(&part_etag_path(&part_path))
     }
 
-    /// Fetch all data files from GitHub Releases.
-    pub async fn fetch_all_data(&self, force_refresh: bool) -> Result<()> {
-        // Files to fetch
-        let files = [
-            "benchmarks",
-            "models",
-            "text_to_image",
-            "image_editing",
-            "text_to_speech",
-            "text_to_video",
-            "image_to_video",
-        ];
-
-        // Fetch manifest first
+    /// Fetch all data files listed in the manifest, downloading up to
+    /// [`MAX_CONCURRENT_DOWNLOADS`] at once. Unchanged files are skipped.
+    pub async fn fetch_all_data(&self, progress: &Progress) -> Result<()> {
+        // Fetch manifest first (fetching also saves it locally)
         let manifest = self.fetch_manifest().await?;
 
-        // Fetch each file
-        for name in &files {
-            let filename = format!("{}.parquet", name);
-            if manifest.files.contains_key(&filename) {
-                self.ensure_parquet(name, force_refresh).await?;
-            }
-        }
-
-        // Save manifest locally
-        let manifest_path = self.cache_dir.join("manifest.json");
-        let manifest_json = serde_json::to_string_pretty(&manifest)?;
-        std::fs::write(manifest_path, manifest_json)?;
+        let filenames: Vec<String> = DATA_FILES
+            .iter()
+            .map(|name| format!("{}.parquet", name))
+            .filter(|filename| manifest.files.contains_key(filename))
+            .collect();
+        let results: Vec<Result<()>> = stream::iter(&filenames)
+            .map(|filename| self.sync_parquet(&manifest, filename, progress))
+            .buffer_unordered(MAX_CONCURRENT_DOWNLOADS)
+            .collect()
+            .await;
+        results.into_iter().collect::<Result<()>>()?;
 
-        Ok(())
+        self.update_meta()
     }
 
-    /// Update the remote metadata file.
+    /// Update the remote metadata file after fetching data.
     fn update_meta(&self) -> Result<()> {
-        let meta = RemoteMeta {
-            fetched_at: Utc::now().to_rfc3339(),
-        };
+        let mut meta = self.read_meta().unwrap_or_default();
+        meta.fetched_at = Utc::now().to_rfc3339();
+        meta.tag = self.source.tag.clone();
+        self.write_meta(&meta)
+    }
+
+    /// Remember the ETag of the manifest just saved, and whether its
+    /// signature was verified.
+    fn record_manifest(&self, etag: Option<String>, signed: bool) -> Result<()> {
+        let mut meta = self.read_meta().unwrap_or_default();
+        meta.manifest_url = etag.as_ref().map(|_| self.source.base_url.clone());
+        meta.manifest_etag = etag;
+        meta.manifest_signed = signed;
+        self.write_meta(&meta)
+    }
+
+    fn write_meta(&self, meta: &RemoteMeta) -> Result<()> {
         let meta_path = self.cache_dir.join("remote_meta.json");
-        let content = serde_json::to_string_pretty(&meta)?;
+        let content = serde_json::to_string_pretty(meta)?;
         std::fs::write(meta_path, content)?;
         Ok(())
     }
 
     /// Get the local manifest if available.
     pub fn get_local_manifest(&self) -> Option<DataManifest> {
-        let manifest_path = self.cache_dir.join("manifest.json");
+        let manifest_path = self.cache_dir.join(MANIFEST_FILE);
         if manifest_path.exists() {
             let content = std::fs::read_to_string(manifest_path).ok()?;
             serde_json::from_str(&content).ok()
@@ -258,8 +804,655 @@ impl RemoteDataClient {
     }
 }
 
+/// A fetched file and its ETag.
+struct Fetched {
+    bytes: Vec<u8>,
+    etag: Option<String>,
+}
+
+/// Why a request attempt failed.
+enum Attempt {
+    /// A transient failure (network error, timeout, 5xx or 429) worth retrying.
+    Retry(String),
+    /// A permanent failure, e.g. 404.
+    Fail(AppError),
+}
+
+/// Bytes of a file received so far, and the ETag they belong to.
+///
+/// For Parquet files they are also kept in `<file>.part`, with the ETag in
+/// `<file>.part.etag`, so an interrupted download resumes in the next run.
+#[derive(Default)]
+struct Partial {
+    bytes: Vec<u8>,
+    etag: Option<String>,
+    /// The `.part` file and its path, if the bytes are kept on disk.
+    file: Option<(std::fs::File, PathBuf)>,
+}
+
+impl Partial {
+    /// Keep the bytes in `path`, resuming from the ones already there if
+    /// their ETag was saved.
+    fn open(path: PathBuf) -> Result<Self> {
+        let etag = std::fs::read_to_string(part_etag_path(&path)).ok();
+        let bytes = match etag {
+            Some(_) => std::fs::read(&path).unwrap_or_default(),
+            None => Vec::new(),
+        };
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)?;
+        let mut partial = Self {
+            bytes,
+            etag,
+            file: Some((file, path)),
+        };
+        if partial.etag.is_none() {
+            // Bytes without an ETag can't be resumed safely
+            partial.restart(None)?;
+        }
+        Ok(partial)
+    }
+
+    /// Drop the bytes, for a download starting over from a file with `etag`.
+    fn restart(&mut self, etag: Option<String>) -> Result<()> {
+        self.bytes.clear();
+        if let Some((file, path)) = &self.file {
+            file.set_len(0)?;
+            let etag_path = part_etag_path(path);
+            match &etag {
+                Some(etag) => std::fs::write(etag_path, etag)?,
+                None if etag_path.exists() => std::fs::remove_file(etag_path)?,
+                None => {}
+            }
+        }
+        self.etag = etag;
+        Ok(())
+    }
+
+    /// Add received bytes.
+    fn append(&mut self, chunk: &[u8]) -> Result<()> {
+        if let Some((file, _)) = &mut self.file {
+            file.write_all(chunk)?;
+        }
+        self.bytes.extend_from_slice(chunk);
+        Ok(())
+    }
+
+    /// Take the complete file.
+    fn finish(&mut self) -> Fetched {
+        Fetched {
+            bytes: std::mem::take(&mut self.bytes),
+            etag: self.etag.take(),
+        }
+    }
+}
+
+/// Path of the file holding the ETag of the `.part` file at `path`.
+fn part_etag_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".etag");
+    PathBuf::from(name)
+}
+
+/// Remove a `.part` file and its ETag, ignoring files that don't exist.
+fn remove_part(path: &Path) -> Result<()> {
+    remove_if_exists(path)?;
+    remove_if_exists(&part_etag_path(path))
+}
+
+/// Remove a file, ignoring it if it doesn't exist.
+fn remove_if_exists(path: &Path) -> Result<()> {
+    match std::fs::remove_file(path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+        _ => Ok(()),
+    }
+}
+
+/// Drop bytes that can't be resumed, so the next attempt starts over.
+fn abandon_resume(
+    filename: &str,
+    reason: &str,
+    partial: &mut Partial,
+    progress: &mut FileProgress,
+) -> Attempt {
+    if let Err(e) = partial.restart(None) {
+        return Attempt::Fail(e);
+    }
+    progress.restart();
+    Attempt::Retry(format!("Failed to resume {}: {}", filename, reason))
+}
+
+/// Check if a failed request with `status` is worth retrying.
+fn is_retryable(status: StatusCode) -> bool {
+    status.is_server_error()
+        || status == StatusCode::TOO_MANY_REQUESTS
+        || status == StatusCode::REQUEST_TIMEOUT
+}
+
+/// Get the first byte position of a `Content-Range: bytes <start>-<end>/<size>` header.
+fn content_range_start(response: &reqwest::Response) -> Option<u64> {
+    let value = response.headers().get(CONTENT_RANGE)?.to_str().ok()?;
+    let range = value.strip_prefix("bytes ")?;
+    range.split('-').next()?.trim().parse().ok()
+}
+
+/// Parse manifest JSON.
+fn parse_manifest(bytes: &[u8]) -> Result<DataManifest> {
+    serde_json::from_slice(bytes)
+        .map_err(|e| AppError::Network(format!("Failed to parse manifest: {}", e)))
+}
+
+/// Get the hex SHA256 of some data.
+fn sha256_hex(data: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(data))
+}
+
+/// Get the hex SHA256 of a file, or `None` if it can't be read.
+fn file_sha256(path: &Path) -> Option<String> {
+    std::fs::read(path).ok().map(|data| sha256_hex(&data))
+}
+
 /// Metadata about when remote data was fetched.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 struct RemoteMeta {
     fetched_at: String,
+    /// GitHub release tag (absent for mirrors).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    tag: Option<String>,
+    /// ETag of the cached manifest, for conditional requests.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    manifest_etag: Option<String>,
+    /// Base URL the ETag applies to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    manifest_url: Option<String>,
+    /// Whether the manifest's signature was verified.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    manifest_signed: bool,
+}
+
+impl RemoteMeta {
+    /// Time since the data was fetched.
+    fn age(&self) -> Option<chrono::Duration> {
+        let fetched_at = DateTime::parse_from_rfc3339(&self.fetched_at).ok()?;
+        Some(Utc::now().signed_duration_since(fetched_at.with_timezone(&Utc)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// Write a release with one parquet file to `dir`, listing `sha256` for it.
+    fn write_release(dir: &std::path::Path, data: &[u8], sha256: &str) {
+        let manifest = serde_json::json!({
+            "generated_at": "2026-09-01T00:00:00Z",
+            "version": "1",
+            "source": { "artificial_analysis": "api", "models_dev": "api" },
+            "files": { "benchmarks.parquet": { "size": data.len(), "sha256": sha256 } },
+            "attribution": { "text": "Artificial Analysis", "url": "https://artificialanalysis.ai" }
+        });
+        std::fs::write(dir.join("manifest.json"), manifest.to_string()).unwrap();
+        std::fs::write(dir.join("benchmarks.parquet"), data).unwrap();
+    }
+
+    #[test]
+    fn test_parse_base_url() {
+        assert_eq!(
+            parse_base_url("https://mirror.example.com/which-llm/")
+                .unwrap()
+                .as_str(),
+            "https://mirror.example.com/which-llm/"
+        );
+        assert!(parse_base_url("file:///srv/which-llm").is_ok());
+        assert!(parse_base_url("ftp://mirror.example.com")
+            .unwrap_err()
+            .to_string()
+            .contains("Unsupported data URL scheme 'ftp'"));
+        assert!(parse_base_url("not a url").is_err());
+    }
+
+    #[test]
+    fn test_data_source() {
+        let latest = DataSource::from_parts(None, None).unwrap();
+        assert_eq!(
+            latest.base_url,
+            "https://github.com/richard-gyiko/which-llm/releases/download/data/latest"
+        );
+        assert!(!latest.is_pinned());
+
+        let pinned = DataSource::from_parts(None, Some("data/2026-10-01")).unwrap();
+        assert!(pinned.base_url.ends_with("/download/data/2026-10-01"));
+        assert!(pinned.is_pinned());
+
+        let mirror = DataSource::from_parts(Some("file:///srv/which-llm".into()), None).unwrap();
+        assert_eq!(mirror.tag, None);
+        assert!(!mirror.signatures.release);
+        assert!(
+            DataSource::from_parts(Some("file:///srv/which-llm".into()), Some("data/latest"))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_unsigned_historical_release() {
+        assert!(is_signed_release("data/latest"));
+        assert!(is_signed_release(FIRST_SIGNED_RELEASE));
+        assert!(is_signed_release("data/2027-01-05"));
+        assert!(!is_signed_release("data/2026-09-01"));
+
+        // A release from before signing started is accepted unsigned, even
+        // by a CLI built with the release key
+        let pinned = DataSource::github("data/2026-09-01");
+        assert!(!pinned.signatures.release);
+        assert!(!pinned
+            .signatures
+            .verify(b"{}", None, &pinned.base_url)
+            .unwrap());
+    }
+
+    #[test]
+    fn test_pinned_data_stays_fresh() {
+        let cache = TempDir::new().unwrap();
+        let client = |tag: &str| {
+            RemoteDataClient::with_source(cache.path().to_path_buf(), DataSource::github(tag))
+                .unwrap()
+        };
+
+        let pinned = client("data/2026-10-01");
+        assert!(!pinned.is_data_fresh());
+        let meta = RemoteMeta {
+            fetched_at: "2026-10-02T00:00:00Z".into(),
+            tag: Some("data/2026-10-01".into()),
+            ..Default::default()
+        };
+        std::fs::write(
+            cache.path().join("remote_meta.json"),
+            serde_json::to_string(&meta).unwrap(),
+        )
+        .unwrap();
+
+        assert!(pinned.is_data_fresh());
+        assert_eq!(pinned.fetched_tag().as_deref(), Some("data/2026-10-01"));
+        pinned.ensure_cached_release().unwrap();
+        // Another release, or an old fetch of the latest one, is refetched
+        assert!(!client("data/2026-09-01").is_data_fresh());
+        assert!(!client("data/latest").is_data_fresh());
+        let err = client("data/2026-09-01")
+            .ensure_cached_release()
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("from data/2026-10-01, not data/2026-09-01"));
+    }
+
+    #[tokio::test]
+    async fn test_offline_uses_stale_cache() {
+        let cache = TempDir::new().unwrap();
+        // Nothing listens here; offline mode must not try it
+        let source = DataSource {
+            offline: true,
+            ..DataSource::mirror("http://127.0.0.1:9/which-llm")
+        };
+        let client = RemoteDataClient::with_source(cache.path().to_path_buf(), source).unwrap();
+        std::fs::write(cache.path().join("benchmarks.parquet"), "cached").unwrap();
+        let meta = RemoteMeta {
+            fetched_at: "2026-01-01T00:00:00Z".into(),
+            ..Default::default()
+        };
+        std::fs::write(
+            cache.path().join("remote_meta.json"),
+            serde_json::to_string(&meta).unwrap(),
+        )
+        .unwrap();
+
+        let stale = client.ensure_parquet("benchmarks", false).await;
+        let missing = client.ensure_parquet("models", false).await;
+        let forced = client.ensure_parquet("benchmarks", true).await;
+
+        assert_eq!(stale.unwrap(), cache.path().join("benchmarks.parquet"));
+        assert!(missing
+            .unwrap_err()
+            .to_string()
+            .contains("models.parquet is not cached"));
+        assert!(forced
+            .unwrap_err()
+            .to_string()
+            .contains("can't fetch manifest.json"));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_from_file_url() {
+        let release = TempDir::new().unwrap();
+        let cache = TempDir::new().unwrap();
+        let data = b"parquet bytes";
+        let sha256 = format!("{:x}", Sha256::digest(data));
+        write_release(release.path(), data, &sha256);
+
+        let source = DataSource::mirror(Url::from_directory_path(release.path()).unwrap());
+        let client =
+            RemoteDataClient::with_source(cache.path().to_path_buf(), source.clone()).unwrap();
+        assert_eq!(client.fetch_manifest().await.unwrap().version, "1");
+
+        let path = client.ensure_parquet("benchmarks", true).await.unwrap();
+        assert_eq!(std::fs::read(path).unwrap(), data);
+
+        // Same checksum verification as GitHub (a new client refetches the manifest)
+        write_release(release.path(), data, "0000");
+        let client = RemoteDataClient::with_source(cache.path().to_path_buf(), source).unwrap();
+        let err = client.ensure_parquet("benchmarks", true).await.unwrap_err();
+        assert!(err.to_string().contains("SHA256 mismatch"));
+
+        let err = client.fetch_parquet("models.parquet").await.unwrap_err();
+        assert!(err.to_string().contains("Not found: models.parquet"));
+    }
+
+    #[tokio::test]
+    async fn test_refresh_skips_unchanged_files() {
+        let release = TempDir::new().unwrap();
+        let cache = TempDir::new().unwrap();
+        let data = b"parquet bytes";
+        write_release(release.path(), data, &sha256_hex(data));
+        std::fs::write(cache.path().join("benchmarks.parquet"), data).unwrap();
+
+        // The release file differs, but the manifest hash matches the cache
+        std::fs::write(release.path().join("benchmarks.parquet"), "changed").unwrap();
+        let source = DataSource::mirror(Url::from_directory_path(release.path()).unwrap());
+        let client = RemoteDataClient::with_source(cache.path().to_path_buf(), source).unwrap();
+        client.fetch_all_data(&Progress::Hidden).await.unwrap();
+
+        assert_eq!(
+            std::fs::read(cache.path().join("benchmarks.parquet")).unwrap(),
+            data
+        );
+        assert!(client.is_data_fresh());
+    }
+
+    #[tokio::test]
+    async fn test_manifest_etag() {
+        use wiremock::matchers::{header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        let release = TempDir::new().unwrap();
+        write_release(release.path(), b"", "0000");
+        let manifest = std::fs::read(release.path().join("manifest.json")).unwrap();
+        Mock::given(method("GET"))
+            .and(path("/manifest.json"))
+            .and(header("if-none-match", "\"v1\""))
+            .respond_with(ResponseTemplate::new(304))
+            .with_priority(1)
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/manifest.json"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("etag", "\"v1\"")
+                    .set_body_bytes(manifest),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let cache = TempDir::new().unwrap();
+        let source = DataSource::mirror(server.uri());
+        let client = |source: &DataSource| {
+            RemoteDataClient::with_source(cache.path().to_path_buf(), source.clone()).unwrap()
+        };
+
+        // Fetched once per client, then revalidated by the next one
+        let first = client(&source);
+        assert_eq!(first.fetch_manifest().await.unwrap().version, "1");
+        assert_eq!(first.fetch_manifest().await.unwrap().version, "1");
+        assert_eq!(client(&source).fetch_manifest().await.unwrap().version, "1");
+    }
+
+    /// Client for a mock server, retrying twice without waiting long.
+    fn flaky_client(cache: &TempDir, server: &wiremock::MockServer) -> RemoteDataClient {
+        let mut source = DataSource::mirror(server.uri());
+        source.fetch = FetchOptions {
+            read_timeout: Duration::from_millis(200),
+            retries: 2,
+            backoff: Duration::from_millis(1),
+            ..FetchOptions::default()
+        };
+        RemoteDataClient::with_source(cache.path().to_path_buf(), source).unwrap()
+    }
+
+    #[test]
+    fn test_retry_delay() {
+        let options = FetchOptions::default();
+        for attempt in 0..3 {
+            let delay = options.retry_delay(attempt);
+            let full = options.backoff * 2u32.pow(attempt);
+            assert!(delay >= full / 2 && delay <= full);
+        }
+        assert!(options.retry_delay(20) <= MAX_BACKOFF);
+
+        let config = DataConfig {
+            read_timeout: Some(5),
+            retries: Some(0),
+            ..Default::default()
+        };
+        let options = FetchOptions::from_config(&config);
+        assert_eq!(options.read_timeout, Duration::from_secs(5));
+        assert_eq!(options.retries, 0);
+        assert_eq!(options.connect_timeout, Duration::from_secs(10));
+    }
+
+    #[tokio::test]
+    async fn test_retries_transient_errors() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        // Two failures, then the file
+        Mock::given(method("GET"))
+            .and(path("/benchmarks.parquet"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(2)
+            .with_priority(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/benchmarks.parquet"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("parquet bytes"))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/models.parquet"))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(3)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/missing.parquet"))
+            .respond_with(ResponseTemplate::new(404))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let cache = TempDir::new().unwrap();
+        let client = flaky_client(&cache, &server);
+        assert_eq!(
+            client.fetch_parquet("benchmarks.parquet").await.unwrap(),
+            b"parquet bytes"
+        );
+
+        let err = client.fetch_parquet("models.parquet").await.unwrap_err();
+        assert!(err.to_string().contains("HTTP 500"));
+        assert!(err.to_string().contains("after 3 attempts"));
+
+        // Permanent errors aren't retried
+        let err = client.fetch_parquet("missing.parquet").await.unwrap_err();
+        assert!(!err.to_string().contains("attempts"));
+    }
+
+    #[tokio::test]
+    async fn test_retries_after_timeout() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/benchmarks.parquet"))
+            .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_secs(2)))
+            .up_to_n_times(1)
+            .with_priority(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/benchmarks.parquet"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("parquet bytes"))
+            .mount(&server)
+            .await;
+
+        let cache = TempDir::new().unwrap();
+        let client = flaky_client(&cache, &server);
+        assert_eq!(
+            client.fetch_parquet("benchmarks.parquet").await.unwrap(),
+            b"parquet bytes"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resumes_partial_download() {
+        use wiremock::matchers::{header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/benchmarks.parquet"))
+            .and(header("range", "bytes=8-"))
+            .and(header("if-range", "\"v1\""))
+            .respond_with(
+                ResponseTemplate::new(206)
+                    .insert_header("content-range", "bytes 8-12/13")
+                    .set_body_string("bytes"),
+            )
+            .with_priority(1)
+            .mount(&server)
+            .await;
+        // The file changed: If-Range doesn't match, so all of it is sent
+        Mock::given(method("GET"))
+            .and(path("/benchmarks.parquet"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("etag", "\"v2\"")
+                    .set_body_string("new parquet bytes"),
+            )
+            .mount(&server)
+            .await;
+
+        let cache = TempDir::new().unwrap();
+        let client = flaky_client(&cache, &server);
+        let url = client.base_url.join("benchmarks.parquet").unwrap();
+        let mut progress = FileProgress::hidden("benchmarks.parquet");
+        let partial = |etag: &str| Partial {
+            bytes: b"parquet ".to_vec(),
+            etag: Some(etag.to_string()),
+            file: None,
+        };
+
+        let resumed = client
+            .fetch_attempt(&url, "x", None, &mut partial("\"v1\""), &mut progress)
+            .await;
+        assert_eq!(resumed.ok().flatten().unwrap().bytes, b"parquet bytes");
+
+        let restarted = client
+            .fetch_attempt(&url, "x", None, &mut partial("\"v0\""), &mut progress)
+            .await
+            .ok()
+            .flatten()
+            .unwrap();
+        assert_eq!(restarted.bytes, b"new parquet bytes");
+        assert_eq!(restarted.etag.as_deref(), Some("\"v2\""));
+    }
+
+    #[tokio::test]
+    async fn test_resumes_download_from_part_file() {
+        use wiremock::matchers::{header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/benchmarks.parquet"))
+            .and(header("range", "bytes=8-"))
+            .and(header("if-range", "\"v1\""))
+            .respond_with(
+                ResponseTemplate::new(206)
+                    .insert_header("content-range", "bytes 8-12/13")
+                    .set_body_string("bytes"),
+            )
+            .mount(&server)
+            .await;
+
+        let cache = TempDir::new().unwrap();
+        let client = flaky_client(&cache, &server);
+        let local_path = cache.path().join("benchmarks.parquet");
+        let part_path = cache.path().join("benchmarks.parquet.part");
+        let write_part = || {
+            std::fs::write(&part_path, "parquet ").unwrap();
+            std::fs::write(part_etag_path(&part_path), "\"v1\"").unwrap();
+        };
+        let manifest = |sha256: &str| {
+            let release = TempDir::new().unwrap();
+            write_release(release.path(), b"", sha256);
+            parse_manifest(&std::fs::read(release.path().join(MANIFEST_FILE)).unwrap()).unwrap()
+        };
+
+        // Bytes that don't match the manifest are dropped, not resumed again
+        write_part();
+        let err = client
+            .sync_parquet(
+                &manifest(&sha256_hex(b"other")),
+                "benchmarks.parquet",
+                &Progress::Hidden,
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("SHA256 mismatch"));
+        assert!(!local_path.exists());
+        assert!(!part_path.exists());
+
+        write_part();
+        client
+            .sync_parquet(
+                &manifest(&sha256_hex(b"parquet bytes")),
+                "benchmarks.parquet",
+                &Progress::Hidden,
+            )
+            .await
+            .unwrap();
+        assert_eq!(std::fs::read(&local_path).unwrap(), b"parquet bytes");
+        assert!(!part_path.exists());
+        assert!(!part_etag_path(&part_path).exists());
+    }
+
+    #[tokio::test]
+    async fn test_unsigned_manifest_refused() {
+        let release = TempDir::new().unwrap();
+        let cache = TempDir::new().unwrap();
+        write_release(release.path(), b"", &sha256_hex(b""));
+
+        let mut source = DataSource::mirror(Url::from_directory_path(release.path()).unwrap());
+        source.signatures.required = true;
+        let client = RemoteDataClient::with_source(cache.path().to_path_buf(), source).unwrap();
+        let err = client.fetch_manifest().await.unwrap_err();
+        assert!(err.to_string().contains("is not signed"));
+        assert!(!cache.path().join(MANIFEST_FILE).exists());
+
+        // A signature that doesn't decode is refused even when not required
+        std::fs::write(release.path().join(SIGNATURE_FILE), "not a signature").unwrap();
+        let mut source = DataSource::mirror(Url::from_directory_path(release.path()).unwrap());
+        source.signatures.trusted_keys =
+            vec!["RWSln4uN8SYntOzeNUaWBVMtSFiblaX6U7AhIpaqkQHCRdhxv9XHpywR".into()];
+        let client = RemoteDataClient::with_source(cache.path().to_path_buf(), source).unwrap();
+        let err = client.fetch_manifest().await.unwrap_err();
+        assert!(err.to_string().contains("invalid manifest.json.minisig"));
+    }
 }
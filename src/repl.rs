@@ -0,0 +1,509 @@
+//! Interactive SQL shell for `which-llm query`.
+//!
+//! Keeps prompt history across sessions and tab-completes table, view and
+//! column names. Input holding several `;`-separated statements runs them in
+//! order, like a script.
+
+use crate::error::{AppError, Result};
+use crate::output::OutputFormat;
+use crate::query::script::{returns_rows, split_statements};
+use crate::query::{
+    format_query_result, format_tables_list, QueryExecutor, QueryResult, TableInfo,
+};
+use crate::schema::{self, ALL_TABLES};
+use rustyline::completion::{Completer, Pair};
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::history::DefaultHistory;
+use rustyline::validate::Validator;
+use rustyline::{Context, Editor, Helper};
+use std::path::PathBuf;
+
+/// History file name (stored in the cache directory).
+const HISTORY_FILE: &str = "query_history.txt";
+
+/// Prompt for a new statement.
+const PROMPT: &str = "which-llm> ";
+
+/// Prompt for a continuation line.
+const CONTINUATION_PROMPT: &str = "       ...> ";
+
+/// Dot-commands understood by the shell.
+const DOT_COMMANDS: &[&str] = &[".tables", ".schema", ".format", ".help", ".quit", ".exit"];
+
+/// Output formats accepted by `.format`.
+const FORMAT_NAMES: &[&str] = &["markdown", "json", "csv", "table", "plain"];
+
+/// A parsed dot-command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum DotCommand {
+    Tables,
+    Schema(Option<String>),
+    Format(Option<String>),
+    Help,
+    Quit,
+}
+
+impl DotCommand {
+    /// Parse a dot-command line (e.g. `.schema benchmarks`).
+    fn parse(line: &str) -> Result<Self> {
+        let mut parts = line.split_whitespace();
+        let command = parts.next().unwrap_or_default();
+        let arg = parts.next().map(|s| s.trim_end_matches(';').to_string());
+
+        match command.to_lowercase().as_str() {
+            ".tables" => Ok(Self::Tables),
+            ".schema" => Ok(Self::Schema(arg)),
+            ".format" | ".mode" => Ok(Self::Format(arg)),
+            ".help" => Ok(Self::Help),
+            ".quit" | ".exit" => Ok(Self::Quit),
+            _ => Err(AppError::Query(format!(
+                "Unknown command '{}'. Type .help for a list of commands.",
+                command
+            ))),
+        }
+    }
+}
+
+/// Line editor helper providing tab completion.
+struct SqlHelper {
+    /// Table and view names registered on the connection.
+    tables: Vec<String>,
+    /// SQL completion candidates (table names followed by column names).
+    words: Vec<String>,
+}
+
+impl SqlHelper {
+    /// Complete the given table and view names, and the tables' columns.
+    fn new(tables: Vec<String>) -> Self {
+        let mut words = tables.clone();
+        for table_def in ALL_TABLES {
+            for col in table_def.columns {
+                if !words.iter().any(|w| w == col.name) {
+                    words.push(col.name.to_string());
+                }
+            }
+        }
+        Self { tables, words }
+    }
+
+    /// Get completion candidates for the word ending at `pos`.
+    fn candidates(&self, line: &str, pos: usize) -> (usize, Vec<String>) {
+        let before = &line[..pos];
+        let start = before
+            .char_indices()
+            .rev()
+            .find(|(_, c)| !(c.is_alphanumeric() || *c == '_' || *c == '.'))
+            .map(|(i, c)| i + c.len_utf8())
+            .unwrap_or(0);
+        let word = &before[start..];
+
+        // Dot-commands and their arguments
+        if before.trim_start().starts_with('.') {
+            let trimmed = before.trim_start();
+            if !trimmed.contains(char::is_whitespace) {
+                let matches = DOT_COMMANDS
+                    .iter()
+                    .filter(|c| c.starts_with(word))
+                    .map(|c| c.to_string())
+                    .collect();
+                return (start, matches);
+            }
+
+            let options: Vec<&str> = if trimmed.starts_with(".schema") {
+                self.tables.iter().map(String::as_str).collect()
+            } else if trimmed.starts_with(".format") || trimmed.starts_with(".mode") {
+                FORMAT_NAMES.to_vec()
+            } else {
+                Vec::new()
+            };
+            let matches = options
+                .iter()
+                .filter(|o| o.starts_with(word))
+                .map(|o| o.to_string())
+                .collect();
+            return (start, matches);
+        }
+
+        // Qualified names (alias.column) complete only the column part
+        let (start, word) = match word.rfind('.') {
+            Some(dot) => (start + dot + 1, &word[dot + 1..]),
+            None => (start, word),
+        };
+
+        if word.is_empty() {
+            return (start, Vec::new());
+        }
+
+        let word_lower = word.to_lowercase();
+        let matches = self
+            .words
+            .iter()
+            .filter(|w| w.starts_with(&word_lower))
+            .cloned()
+            .collect();
+        (start, matches)
+    }
+}
+
+impl Completer for SqlHelper {
+    type Candidate = Pair;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<Pair>)> {
+        let (start, matches) = self.candidates(line, pos);
+        let pairs = matches
+            .into_iter()
+            .map(|m| Pair {
+                display: m.clone(),
+                replacement: m,
+            })
+            .collect();
+        Ok((start, pairs))
+    }
+}
+
+impl Hinter for SqlHelper {
+    type Hint = String;
+}
+
+impl Highlighter for SqlHelper {}
+
+impl Validator for SqlHelper {}
+
+impl Helper for SqlHelper {}
+
+/// Interactive SQL shell state.
+pub struct Repl {
+    executor: QueryExecutor,
+    format: OutputFormat,
+    precision: usize,
+    history_path: PathBuf,
+}
+
+impl Repl {
+    /// Create a new shell over the given executor.
+    pub fn new(
+        executor: QueryExecutor,
+        format: OutputFormat,
+        precision: usize,
+        history_path: PathBuf,
+    ) -> Self {
+        Self {
+            executor,
+            format,
+            precision,
+            history_path,
+        }
+    }
+
+    /// Get the default history file path inside the cache directory.
+    pub fn default_history_path(cache_dir: &std::path::Path) -> PathBuf {
+        cache_dir.join(HISTORY_FILE)
+    }
+
+    /// Run the read-eval-print loop until EOF or `.quit`.
+    pub fn run(&mut self) -> Result<()> {
+        let mut editor: Editor<SqlHelper, DefaultHistory> =
+            Editor::new().map_err(|e| AppError::Query(format!("Line editor error: {}", e)))?;
+        let tables = self.executor.list_tables().into_iter().map(|t| t.name);
+        editor.set_helper(Some(SqlHelper::new(tables.collect())));
+        let _ = editor.load_history(&self.history_path);
+
+        println!(
+            "which-llm v{} interactive SQL shell",
+            env!("CARGO_PKG_VERSION")
+        );
+        println!("Enter SQL statements terminated by ';'. Type .help for commands.");
+
+        let mut buffer = String::new();
+
+        loop {
+            let prompt = if buffer.is_empty() {
+                PROMPT
+            } else {
+                CONTINUATION_PROMPT
+            };
+
+            let line = match editor.readline(prompt) {
+                Ok(line) => line,
+                Err(ReadlineError::Interrupted) => {
+                    // Ctrl-C discards the current statement
+                    buffer.clear();
+                    continue;
+                }
+                Err(ReadlineError::Eof) => break,
+                Err(e) => return Err(AppError::Query(format!("Line editor error: {}", e))),
+            };
+
+            let trimmed = line.trim();
+            if trimmed.is_empty() {
+                continue;
+            }
+
+            // Dot-commands are only recognized at the start of a statement
+            if buffer.is_empty() && trimmed.starts_with('.') {
+                let _ = editor.add_history_entry(trimmed);
+                match DotCommand::parse(trimmed) {
+                    Ok(DotCommand::Quit) => break,
+                    Ok(command) => self.handle_dot_command(command),
+                    Err(e) => eprintln!("Error: {}", e),
+                }
+                continue;
+            }
+
+            if !buffer.is_empty() {
+                buffer.push('\n');
+            }
+            buffer.push_str(&line);
+
+            if !trimmed.ends_with(';') {
+                continue;
+            }
+
+            let sql = std::mem::take(&mut buffer);
+            let _ = editor.add_history_entry(sql.as_str());
+
+            // Results print as each statement finishes; an error stops the rest
+            if let Err(e) = self.execute_script(&sql, |result| self.print_result(&result)) {
+                eprintln!("Error: {}", e);
+            }
+        }
+
+        if let Some(parent) = self.history_path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        let _ = editor.save_history(&self.history_path);
+
+        Ok(())
+    }
+
+    /// Run each statement of `sql`, passing the results of those that
+    /// return rows to `output`.
+    fn execute_script(&self, sql: &str, mut output: impl FnMut(QueryResult)) -> Result<()> {
+        for statement in split_statements(sql) {
+            let result = self.executor.execute(&statement)?;
+            if returns_rows(&statement) {
+                output(result);
+            }
+        }
+        Ok(())
+    }
+
+    /// Print a statement's result, warning if it was truncated.
+    fn print_result(&self, result: &QueryResult) {
+        println!(
+            "{}",
+            format_query_result(result, self.format, self.precision)
+        );
+        if let Some(truncation) = result.truncated {
+            eprintln!("Warning: {}", truncation);
+        }
+    }
+
+    /// Get the schema of a table or view as SQL.
+    fn schema_sql(&self, name: &str) -> Result<String> {
+        let name = name.to_lowercase();
+        if let Some(table_def) = schema::get_table_def(&name) {
+            return Ok(format!("{};", table_def.create_table_sql()));
+        }
+        let tables = self.executor.list_tables();
+        match tables.iter().find(|t| t.name == name) {
+            Some(TableInfo {
+                view_sql: Some(sql),
+                ..
+            }) => Ok(format!("CREATE VIEW {} AS {};", name, sql)),
+            Some(TableInfo {
+                schema: Some(columns),
+                ..
+            }) => {
+                let columns: Vec<String> = columns
+                    .iter()
+                    .map(|col| format!("{} {}", col.name, col.data_type))
+                    .collect();
+                Ok(format!(
+                    "CREATE VIEW {} (\n    {}\n);",
+                    name,
+                    columns.join(",\n    ")
+                ))
+            }
+            Some(_) => Err(AppError::Query(format!(
+                "View '{}' is not available. Run 'which-llm refresh' first.",
+                name
+            ))),
+            None => Err(AppError::Query(format!("Table '{}' not found.", name))),
+        }
+    }
+
+    /// Execute a dot-command.
+    fn handle_dot_command(&mut self, command: DotCommand) {
+        match command {
+            DotCommand::Tables => {
+                for table in self.executor.list_tables() {
+                    let status = if table.exists { "" } else { " (not cached)" };
+                    println!("{}{}", table.name, status);
+                }
+            }
+            DotCommand::Schema(None) => {
+                println!("{}", format_tables_list(&self.executor.list_tables()));
+            }
+            DotCommand::Schema(Some(name)) => match self.schema_sql(&name) {
+                Ok(sql) => println!("{}", sql),
+                Err(e) => eprintln!("Error: {}", e),
+            },
+            DotCommand::Format(None) => println!("Current format: {}", self.format.name()),
+            DotCommand::Format(Some(name)) => match OutputFormat::parse(&name) {
+                Some(format) => self.format = format,
+                None => eprintln!(
+                    "Error: Unknown format '{}'. Use one of: {}",
+                    name,
+                    FORMAT_NAMES.join(", ")
+                ),
+            },
+            DotCommand::Help => print_help(),
+            DotCommand::Quit => {}
+        }
+    }
+}
+
+/// Print the shell help text.
+fn print_help() {
+    println!(".tables               List available tables");
+    println!(".schema [TABLE]       Show the schema of a table or view (or all of them)");
+    println!(".format [FORMAT]      Show or set the output format");
+    println!("                      (markdown, json, csv, table, plain)");
+    println!(".help                 Show this help");
+    println!(".quit                 Exit the shell");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::query::QueryOptions;
+    use crate::schema::ALL_VIEWS;
+    use tempfile::TempDir;
+
+    fn helper() -> SqlHelper {
+        let tables = ALL_TABLES
+            .iter()
+            .map(|t| t.name)
+            .chain(ALL_VIEWS.iter().map(|v| v.name));
+        SqlHelper::new(tables.map(String::from).collect())
+    }
+
+    fn repl(temp_dir: &TempDir, options: QueryOptions) -> Repl {
+        let executor = QueryExecutor::with_options(temp_dir.path().to_path_buf(), options).unwrap();
+        Repl::new(
+            executor,
+            OutputFormat::Json,
+            2,
+            temp_dir.path().join(HISTORY_FILE),
+        )
+    }
+
+    #[test]
+    fn test_dot_command_parse() {
+        assert_eq!(DotCommand::parse(".tables").unwrap(), DotCommand::Tables);
+        assert_eq!(
+            DotCommand::parse(".schema benchmarks").unwrap(),
+            DotCommand::Schema(Some("benchmarks".into()))
+        );
+        assert_eq!(
+            DotCommand::parse(".format json").unwrap(),
+            DotCommand::Format(Some("json".into()))
+        );
+        assert_eq!(DotCommand::parse(".exit").unwrap(), DotCommand::Quit);
+        assert!(DotCommand::parse(".bogus").is_err());
+    }
+
+    #[test]
+    fn test_complete_table_and_column_names() {
+        let helper = helper();
+
+        let (start, matches) = helper.candidates("SELECT * FROM bench", 19);
+        assert_eq!(start, 14);
+        assert_eq!(matches, vec!["benchmarks".to_string()]);
+
+        let (_, matches) = helper.candidates("SELECT intel", 12);
+        assert_eq!(matches, vec!["intelligence".to_string()]);
+
+        let (start, matches) = helper.candidates("SELECT b.context_w", 18);
+        assert_eq!(start, 9);
+        assert_eq!(matches, vec!["context_window".to_string()]);
+        // Multi-byte separators before the word
+        let line = "SELECT 1 →intel";
+        let (start, matches) = helper.candidates(line, line.len());
+        assert_eq!(&line[start..], "intel");
+        assert_eq!(matches, vec!["intelligence".to_string()]);
+    }
+
+    #[test]
+    fn test_complete_dot_commands() {
+        let helper = helper();
+
+        let (start, matches) = helper.candidates(".sch", 4);
+        assert_eq!(start, 0);
+        assert_eq!(matches, vec![".schema".to_string()]);
+
+        let (_, matches) = helper.candidates(".schema text_to_i", 17);
+        assert_eq!(matches, vec!["text_to_image".to_string()]);
+
+        let (_, matches) = helper.candidates(".format j", 9);
+        assert_eq!(matches, vec!["json".to_string()]);
+    }
+
+    #[test]
+    fn test_multiple_statements() {
+        let temp_dir = TempDir::new().unwrap();
+        let options = QueryOptions {
+            sandbox: false,
+            ..Default::default()
+        };
+        let repl = repl(&temp_dir, options);
+
+        let mut results = Vec::new();
+        repl.execute_script(
+            "CREATE TEMP TABLE t AS SELECT 1 AS n; SELECT n FROM t; SELECT 2 AS m;",
+            |result| results.push(result.columns),
+        )
+        .unwrap();
+        assert_eq!(results, vec![vec!["n".to_string()], vec!["m".to_string()]]);
+
+        // An error stops the statements after it
+        let mut count = 0;
+        assert!(repl
+            .execute_script("SELECT 1; SELECT * FROM nope; SELECT 2;", |_| count += 1)
+            .is_err());
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn test_schema_of_views() {
+        let temp_dir = TempDir::new().unwrap();
+        let options = QueryOptions {
+            views: [("cheap".to_string(), "SELECT 1 AS n".to_string())].into(),
+            ..Default::default()
+        };
+        let repl = repl(&temp_dir, options);
+
+        assert!(repl
+            .schema_sql("benchmarks")
+            .unwrap()
+            .starts_with("CREATE TABLE benchmarks"));
+        assert_eq!(
+            repl.schema_sql("cheap").unwrap(),
+            "CREATE VIEW cheap AS SELECT 1 AS n;"
+        );
+        assert!(repl
+            .schema_sql("llms")
+            .unwrap_err()
+            .to_string()
+            .contains("not available"));
+        assert!(repl.schema_sql("nope").is_err());
+    }
+}
@@ -162,6 +162,125 @@ pub const BENCHMARKS: TableDef = TableDef {
     ],
 };
 
+/// `benchmarks` score columns.
+pub const SCORE_FIELDS: &[&str] = &[
+    "intelligence",
+    "coding",
+    "math",
+    "mmlu_pro",
+    "gpqa",
+    "hle",
+    "livecodebench",
+    "scicode",
+    "math_500",
+    "aime",
+];
+
+/// `benchmarks` price columns.
+pub const PRICE_FIELDS: &[&str] = &["input_price", "output_price", "price"];
+
+/// `benchmarks` columns that `which-llm trend` can chart: scores, prices,
+/// then speed.
+pub const TREND_FIELDS: &[&str] = &[
+    "intelligence",
+    "coding",
+    "math",
+    "mmlu_pro",
+    "gpqa",
+    "hle",
+    "livecodebench",
+    "scicode",
+    "math_500",
+    "aime",
+    "input_price",
+    "output_price",
+    "price",
+    "tps",
+    "latency",
+];
+
+/// Crosswalk table schema - links benchmarks to models.dev entries.
+///
+/// Built locally on refresh from `benchmarks` and `models`, with the user's
+/// overrides applied (see `crate::crosswalk`).
+pub const CROSSWALK: TableDef = TableDef {
+    name: "crosswalk",
+    command: "which-llm refresh",
+    parquet_file: "crosswalk.parquet",
+    columns: &[
+        Column {
+            name: "aa_id",
+            sql_type: "VARCHAR",
+            nullable: false,
+        },
+        Column {
+            name: "aa_slug",
+            sql_type: "VARCHAR",
+            nullable: false,
+        },
+        Column {
+            name: "provider_id",
+            sql_type: "VARCHAR",
+            nullable: false,
+        },
+        Column {
+            name: "model_id",
+            sql_type: "VARCHAR",
+            nullable: false,
+        },
+        Column {
+            name: "confidence",
+            sql_type: "DOUBLE",
+            nullable: false,
+        },
+        Column {
+            name: "method",
+            sql_type: "VARCHAR",
+            nullable: false,
+        },
+    ],
+};
+
+/// A built-in view derived from other tables.
+#[derive(Debug, Clone, Copy)]
+pub struct ViewDef {
+    pub name: &'static str,
+    /// Tables that must be cached for the view to be registered.
+    pub requires: &'static [&'static TableDef],
+    pub sql: &'static str,
+}
+
+/// Unified LLM view: one row per benchmarked model, with capabilities from
+/// every models.dev entry linked through the crosswalk.
+pub const LLMS: ViewDef = ViewDef {
+    name: "llms",
+    requires: &[&BENCHMARKS, &MODELS, &CROSSWALK],
+    sql: "WITH caps AS (
+        SELECT
+            c.aa_id,
+            bool_or(m.tool_call) AS tool_call,
+            bool_or(m.structured_output) AS structured_output,
+            bool_or(m.reasoning) AS reasoning,
+            bool_or(m.attachment) AS attachment,
+            bool_or(m.open_weights) AS open_weights,
+            max(m.context_window) AS context_window,
+            max(m.max_output_tokens) AS max_output_tokens,
+            arg_max(m.input_modalities, c.confidence) AS input_modalities,
+            arg_max(m.output_modalities, c.confidence) AS output_modalities,
+            list_sort(list(DISTINCT m.provider_id)) AS providers,
+            max(c.confidence) AS match_confidence
+        FROM crosswalk c
+        JOIN models m ON m.provider_id = c.provider_id AND m.model_id = c.model_id
+        GROUP BY c.aa_id
+    )
+    SELECT b.*, caps.* EXCLUDE (aa_id), caps.aa_id IS NOT NULL AS models_dev_matched
+    FROM benchmarks b
+    LEFT JOIN caps ON caps.aa_id = b.id",
+};
+
+/// All built-in views, registered after the tables they read.
+pub const ALL_VIEWS: &[&ViewDef] = &[&LLMS];
+
 // Media columns (shared by all media tables)
 const MEDIA_COLUMNS: &[Column] = &[
     Column {
@@ -241,6 +360,7 @@ pub const IMAGE_TO_VIDEO: TableDef = TableDef {
 pub const ALL_TABLES: &[&TableDef] = &[
     &BENCHMARKS,
     &MODELS,
+    &CROSSWALK,
     &TEXT_TO_IMAGE,
     &IMAGE_EDITING,
     &TEXT_TO_SPEECH,
@@ -253,6 +373,7 @@ pub fn get_table_def(name: &str) -> Option<&'static TableDef> {
     match name {
         "benchmarks" => Some(&BENCHMARKS),
         "models" => Some(&MODELS),
+        "crosswalk" => Some(&CROSSWALK),
         "text_to_image" => Some(&TEXT_TO_IMAGE),
         "image_editing" => Some(&IMAGE_EDITING),
         "text_to_speech" => Some(&TEXT_TO_SPEECH),
@@ -279,6 +400,14 @@ mod tests {
         assert!(!sql.contains("models_dev_matched"));
     }
 
+    #[test]
+    fn test_trend_fields() {
+        // All of them are benchmarks columns
+        for field in TREND_FIELDS {
+            assert!(BENCHMARKS.columns.iter().any(|c| c.name == *field));
+        }
+    }
+
     #[test]
     fn test_media_tables_share_schema() {
         assert_eq!(TEXT_TO_IMAGE.columns.len(), IMAGE_EDITING.columns.len());
@@ -295,7 +424,7 @@ mod tests {
 
     #[test]
     fn test_all_tables_count() {
-        assert_eq!(ALL_TABLES.len(), 7);
+        assert_eq!(ALL_TABLES.len(), 8);
     }
 
     #[test]
@@ -0,0 +1,226 @@
+//! Manifest signatures.
+//!
+//! A data release can ship `manifest.json.minisig`, a [minisign] signature of
+//! `manifest.json`. It is checked against the public key built into the CLI
+//! and any `trusted_keys` under `[data]` in config.toml. The manifest lists
+//! the SHA256 of every Parquet file, so a valid signature covers those too.
+//!
+//! [minisign]: https://jedisct1.github.io/minisign/
+
+use crate::config::DataConfig;
+use crate::error::{AppError, Result};
+use minisign_verify::{PublicKey, Signature};
+
+/// Signature file name, next to `manifest.json`.
+pub const SIGNATURE_FILE: &str = "manifest.json.minisig";
+
+/// Public key of the data release workflow, set at build time.
+const RELEASE_PUBLIC_KEY: Option<&str> = option_env!("WHICH_LLM_DATA_PUBLIC_KEY");
+
+/// Which manifest signatures are trusted, and whether one is required.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SignaturePolicy {
+    /// minisign public keys trusted besides the built-in one.
+    pub trusted_keys: Vec<String>,
+    /// Refuse manifests without a valid signature.
+    pub required: bool,
+    /// The manifest comes from an official GitHub release. These are signed
+    /// with the built-in key, so one without a signature is refused when the
+    /// CLI has that key.
+    pub release: bool,
+}
+
+impl SignaturePolicy {
+    /// Policy from `[data]` in config.toml.
+    pub fn from_config(data: &DataConfig) -> Self {
+        Self {
+            trusted_keys: data.trusted_keys.clone(),
+            required: data.require_signature,
+            release: false,
+        }
+    }
+
+    /// Check if signatures are checked at all: there is a key to check them
+    /// against, or they are required.
+    pub fn is_enabled(&self) -> bool {
+        self.required || release_public_key().is_some() || !self.trusted_keys.is_empty()
+    }
+
+    /// Parse the trusted public keys.
+    fn public_keys(&self) -> Result<Vec<PublicKey>> {
+        let mut keys = Vec::new();
+        if let Some(key) = release_public_key() {
+            keys.push(parse_release_key(key)?);
+        }
+        for key in &self.trusted_keys {
+            keys.push(parse_public_key(key)?);
+        }
+        Ok(keys)
+    }
+
+    /// Check a manifest against its signature, if any, from `origin`.
+    ///
+    /// Returns whether the manifest was verified. A signature that doesn't
+    /// match any trusted key is always an error; a missing one only when
+    /// signatures are required or the manifest is from an official release.
+    pub fn verify(&self, manifest: &[u8], signature: Option<&[u8]>, origin: &str) -> Result<bool> {
+        let Some(signature) = signature else {
+            if self.release && release_public_key().is_some() {
+                return Err(AppError::Signature(format!(
+                    "the manifest from {} is not signed, but official releases are. It may \
+                     have been tampered with",
+                    origin
+                )));
+            }
+            if self.required {
+                return Err(AppError::Signature(format!(
+                    "the manifest from {} is not signed and require_signature is set under [data]",
+                    origin
+                )));
+            }
+            return Ok(false);
+        };
+
+        let keys = self.public_keys()?;
+        if keys.is_empty() {
+            if self.required {
+                return Err(AppError::Signature(
+                    "no public key to check the manifest signature against. Add one to \
+                     trusted_keys under [data]"
+                        .into(),
+                ));
+            }
+            return Ok(false);
+        }
+
+        let signature = std::str::from_utf8(signature)
+            .ok()
+            .and_then(|text| Signature::decode(text).ok())
+            .ok_or_else(|| {
+                AppError::Signature(format!("invalid {} from {}", SIGNATURE_FILE, origin))
+            })?;
+        if keys
+            .iter()
+            .any(|key| key.verify(manifest, &signature, false).is_ok())
+        {
+            Ok(true)
+        } else {
+            Err(AppError::Signature(format!(
+                "the manifest from {} doesn't match its signature from any trusted key",
+                origin
+            )))
+        }
+    }
+}
+
+/// Get the built-in public key, if the CLI was built with one.
+fn release_public_key() -> Option<&'static str> {
+    builtin_key(RELEASE_PUBLIC_KEY)
+}
+
+/// Ignore an empty build-time key, as set by a release workflow without one.
+fn builtin_key(key: Option<&'static str>) -> Option<&'static str> {
+    key.map(str::trim).filter(|key| !key.is_empty())
+}
+
+/// Parse the built-in public key. A bad one is a broken build, so it is an
+/// error rather than skipped.
+fn parse_release_key(key: &str) -> Result<PublicKey> {
+    PublicKey::from_base64(key).map_err(|_| {
+        AppError::Signature(format!(
+            "the built-in public key '{}' is invalid. Rebuild with a valid \
+             WHICH_LLM_DATA_PUBLIC_KEY",
+            key
+        ))
+    })
+}
+
+/// Parse a minisign public key, either the base64 key alone or the contents
+/// of a `.pub` file (whose last line is the key).
+fn parse_public_key(key: &str) -> Result<PublicKey> {
+    let line = key.lines().map(str::trim).filter(|l| !l.is_empty()).last();
+    line.and_then(|line| PublicKey::from_base64(line).ok())
+        .ok_or_else(|| {
+            AppError::Config(format!(
+                "Invalid minisign public key '{}' in trusted_keys",
+                key
+            ))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &[u8] = br#"{"generated_at":"2026-09-01T00:00:00Z","version":"1","source":{"artificial_analysis":"api","models_dev":"api"},"files":{},"attribution":{"text":"Artificial Analysis","url":"https://artificialanalysis.ai"}}"#;
+    const PUBLIC_KEY: &str = "RWSln4uN8SYntOzeNUaWBVMtSFiblaX6U7AhIpaqkQHCRdhxv9XHpywR";
+    const OTHER_KEY: &str = "RWQTmI3sy+eI7QZY2roSkDAdUqNIsER9m1OLw+cHm2HTUKx6mwUKmTcl";
+    const SIGNATURE: &str = "untrusted comment: signature from minisign secret key
+RUSln4uN8SYntB8GLewHJLVioUosUgEM3qve3+YR0oFzh4oFBv9H+rBiQzRGhmGukCeMs3bsawnX/cQ1ukwR8K8X3Uiui4/BXAY=
+trusted comment: timestamp:1788220800\tfile:manifest.json\tprehashed
+xO2kkZJgfYC+5XtUJXk8M5gdUGBEyWppH6zCR8+kw4sZvPoF8blgZqe7EIC5igRLLyw3pGVrPVVYF+ZJEP8MAw==
+";
+
+    fn policy(key: &str, required: bool) -> SignaturePolicy {
+        SignaturePolicy {
+            trusted_keys: vec![key.to_string()],
+            required,
+            release: false,
+        }
+    }
+
+    #[test]
+    fn test_verify_signature() {
+        let signature = Some(SIGNATURE.as_bytes());
+        assert!(policy(PUBLIC_KEY, true)
+            .verify(MANIFEST, signature, "mirror")
+            .unwrap());
+        // A .pub file works too
+        let pub_file = format!("untrusted comment: minisign public key\n{}\n", PUBLIC_KEY);
+        assert!(policy(&pub_file, false)
+            .verify(MANIFEST, signature, "mirror")
+            .unwrap());
+
+        let mut tampered = MANIFEST.to_vec();
+        tampered[20] = b'8';
+        let err = policy(PUBLIC_KEY, false)
+            .verify(&tampered, signature, "mirror")
+            .unwrap_err();
+        assert!(err.to_string().contains("doesn't match its signature"));
+        assert!(policy(OTHER_KEY, false)
+            .verify(MANIFEST, signature, "mirror")
+            .is_err());
+        assert!(policy(PUBLIC_KEY, false)
+            .verify(MANIFEST, Some(b"garbage"), "mirror")
+            .is_err());
+    }
+
+    #[test]
+    fn test_unsigned_manifest() {
+        assert!(!policy(PUBLIC_KEY, false)
+            .verify(MANIFEST, None, "mirror")
+            .unwrap());
+        let err = policy(PUBLIC_KEY, true)
+            .verify(MANIFEST, None, "mirror")
+            .unwrap_err();
+        assert!(err.to_string().contains("not signed"));
+
+        let err = policy("not a key", false)
+            .verify(MANIFEST, Some(SIGNATURE.as_bytes()), "mirror")
+            .unwrap_err();
+        assert!(err.to_string().contains("Invalid minisign public key"));
+    }
+
+    #[test]
+    fn test_release_key() {
+        assert_eq!(builtin_key(None), None);
+        assert_eq!(builtin_key(Some("")), None);
+        assert_eq!(builtin_key(Some(" \n")), None);
+        assert_eq!(builtin_key(Some("RWSkey\n")), Some("RWSkey"));
+        assert!(parse_release_key(PUBLIC_KEY).is_ok());
+        let err = parse_release_key("garbage").unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("built-in public key 'garbage' is invalid"));
+    }
+}
@@ -20,6 +20,8 @@ fn cmd_with_temp_config(temp: &tempfile::TempDir) -> Command {
 
     // Remove any existing API key
     cmd.env_remove("ARTIFICIAL_ANALYSIS_API_KEY");
+    cmd.env_remove("WHICH_LLM_DATA_URL");
+    cmd.env_remove("WHICH_LLM_OFFLINE");
     cmd
 }
 
@@ -120,3 +122,291 @@ fn test_query_missing_table() {
         .stderr(predicate::str::contains("not found"))
         .stderr(predicate::str::contains("which-llm refresh"));
 }
+
+#[test]
+fn test_query_unknown_table_json_error() {
+    let temp = tempfile::tempdir().unwrap();
+    cmd_with_temp_config(&temp)
+        .arg("query")
+        .arg("SELECT * FROM benchmark")
+        .arg("--json")
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(r#""kind":"unknown_table""#))
+        .stdout(predicate::str::contains(r#""suggestions":["benchmarks"]"#));
+}
+
+#[test]
+fn test_query_save_list_run() {
+    let temp = tempfile::tempdir().unwrap();
+    cmd_with_temp_config(&temp)
+        .args([
+            "query",
+            "save",
+            "cheap-coders",
+            "SELECT name FROM benchmarks WHERE coding > :min_coding",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Saved query 'cheap-coders'"))
+        .stdout(predicate::str::contains("--param min_coding="));
+
+    cmd_with_temp_config(&temp)
+        .args(["query", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("cheap-coders (:min_coding)"));
+
+    cmd_with_temp_config(&temp)
+        .args(["query", "run", "cheap-coders"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Missing value for parameter :min_coding",
+        ));
+
+    cmd_with_temp_config(&temp)
+        .args(["query", "run", "missing"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("saved query 'missing'"));
+}
+
+#[test]
+fn test_query_script_from_stdin() {
+    let temp = tempfile::tempdir().unwrap();
+    cmd_with_temp_config(&temp)
+        .args(["query", "-", "--csv"])
+        .write_stdin(
+            "CREATE TEMP TABLE t AS SELECT 1 AS n;\n\
+             SELECT n FROM t;\n\
+             SELECT n + 1 AS m FROM t;\n",
+        )
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("n\n1"))
+        .stdout(predicate::str::contains("m\n2"));
+}
+
+#[test]
+fn test_config_views() {
+    let temp = tempfile::tempdir().unwrap();
+    let config_dir = temp.path().join("config").join("which-llm");
+    std::fs::create_dir_all(&config_dir).unwrap();
+    std::fs::write(
+        config_dir.join("config.toml"),
+        "[views]\nanswer = \"SELECT 42 AS value\"\n",
+    )
+    .unwrap();
+    // Project views come from which-llm.toml in the working directory
+    std::fs::write(
+        temp.path().join("which-llm.toml"),
+        "[views]\nquestion = \"SELECT value + 1 AS value FROM answer\"\n",
+    )
+    .unwrap();
+
+    cmd_with_temp_config(&temp)
+        .current_dir(temp.path())
+        .arg("tables")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("answer (view from config)"))
+        .stdout(predicate::str::contains("question (view from config)"));
+
+    cmd_with_temp_config(&temp)
+        .current_dir(temp.path())
+        .args(["query", "SELECT value FROM question", "--csv"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("43"));
+}
+
+#[test]
+fn test_as_of_without_snapshots() {
+    let temp = tempfile::tempdir().unwrap();
+    cmd_with_temp_config(&temp)
+        .args(["query", "SELECT 1", "--as-of", "2026-09-01"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "no data snapshot on or before 2026-09-01",
+        ));
+
+    cmd_with_temp_config(&temp)
+        .args(["compare", "gpt-4o", "claude", "--as-of", "last-week"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("expected a date like 2026-09-01"));
+}
+
+#[test]
+fn test_diff_needs_two_snapshots() {
+    let temp = tempfile::tempdir().unwrap();
+    cmd_with_temp_config(&temp)
+        .arg("diff")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("previous data snapshot"));
+
+    cmd_with_temp_config(&temp)
+        .args(["diff", "no-such-dir"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "expected a date (YYYY-MM-DD) or a directory",
+        ));
+}
+
+#[test]
+fn test_watch_without_models() {
+    let temp = tempfile::tempdir().unwrap();
+    cmd_with_temp_config(&temp)
+        .args(["watch", "check", "--json"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("\"checked\": 0")
+                .and(predicate::str::contains("\"violations\": []"))
+                .and(predicate::str::contains("No watched models").not()),
+        );
+
+    cmd_with_temp_config(&temp)
+        .args(["watch", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("which-llm watch add"));
+
+    cmd_with_temp_config(&temp)
+        .args(["watch", "remove", "gpt-4o"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Model 'gpt-4o' is not watched."));
+
+    cmd_with_temp_config(&temp)
+        .args(["watch", "add", "gpt-4o", "--max-price", "cheap"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--max-price"));
+}
+
+#[test]
+fn test_trend_needs_snapshots() {
+    let temp = tempfile::tempdir().unwrap();
+    cmd_with_temp_config(&temp)
+        .args(["trend", "gpt-4o", "--field", "price,latency"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("data snapshots"));
+
+    cmd_with_temp_config(&temp)
+        .args(["trend", "gpt-4o", "--field", "speed"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid value 'speed'"));
+}
+
+#[test]
+fn test_data_tag_conflicts_with_mirror() {
+    let temp = tempfile::tempdir().unwrap();
+    cmd_with_temp_config(&temp)
+        .env("WHICH_LLM_DATA_URL", "file:///srv/which-llm")
+        .args(["--data-tag", "data/2026-10-01", "info"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Data tag 'data/2026-10-01' selects a GitHub release",
+        ));
+}
+
+#[test]
+fn test_data_tag_checks_cached_release() {
+    let temp = tempfile::tempdir().unwrap();
+    for args in [["tables"].as_slice(), &["query", "SELECT 1"]] {
+        cmd_with_temp_config(&temp)
+            .args(["--data-tag", "data/2026-10-01"])
+            .args(args)
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("not data/2026-10-01"));
+    }
+}
+
+#[test]
+fn test_offline_names_missing_file() {
+    let temp = tempfile::tempdir().unwrap();
+    cmd_with_temp_config(&temp)
+        .env("WHICH_LLM_OFFLINE", "1")
+        .args(["compare", "gpt-4o", "claude"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Offline mode: benchmarks.parquet is not cached",
+        ));
+
+    cmd_with_temp_config(&temp)
+        .args(["--offline", "refresh"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("without network access"));
+}
+
+#[test]
+fn test_refresh_progress_events() {
+    let temp = tempfile::tempdir().unwrap();
+    let release = temp.path().join("release");
+    std::fs::create_dir_all(&release).unwrap();
+    std::fs::write(
+        release.join("manifest.json"),
+        r#"{"generated_at": "2026-10-01T00:00:00Z", "version": "1",
+            "source": {"artificial_analysis": "api", "models_dev": "api"},
+            "files": {"benchmarks.parquet": {"size": 4, "sha256": "0000"}},
+            "attribution": {"text": "Artificial Analysis", "url": "https://artificialanalysis.ai"}}"#,
+    )
+    .unwrap();
+    std::fs::write(release.join("benchmarks.parquet"), "data").unwrap();
+    let url = reqwest::Url::from_directory_path(&release).unwrap();
+
+    // Not a terminal: one JSON event per line
+    cmd_with_temp_config(&temp)
+        .env("WHICH_LLM_DATA_URL", url.as_str())
+        .args(["--quiet", "refresh"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            r#""event":"start","file":"benchmarks.parquet","total":4"#,
+        ))
+        .stderr(predicate::str::contains(r#""event":"failed""#))
+        .stderr(predicate::str::contains("SHA256 mismatch"));
+}
+
+#[test]
+fn test_require_signature_refuses_unsigned_data() {
+    let temp = tempfile::tempdir().unwrap();
+    let config_dir = temp.path().join("config").join("which-llm");
+    std::fs::create_dir_all(&config_dir).unwrap();
+    std::fs::write(
+        config_dir.join("config.toml"),
+        "[data]\nrequire_signature = true\n",
+    )
+    .unwrap();
+    let release = temp.path().join("release");
+    std::fs::create_dir_all(&release).unwrap();
+    std::fs::write(
+        release.join("manifest.json"),
+        r#"{"generated_at": "2026-10-01T00:00:00Z", "version": "1",
+            "source": {"artificial_analysis": "api", "models_dev": "api"}, "files": {},
+            "attribution": {"text": "Artificial Analysis", "url": "https://artificialanalysis.ai"}}"#,
+    )
+    .unwrap();
+    let url = reqwest::Url::from_directory_path(&release).unwrap();
+
+    cmd_with_temp_config(&temp)
+        .env("WHICH_LLM_DATA_URL", url.as_str())
+        .args(["--quiet", "refresh"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Signature check failed: the manifest from",
+        ))
+        .stderr(predicate::str::contains("is not signed"));
+}